@@ -0,0 +1,211 @@
+use std::{collections::HashMap, time::SystemTime};
+
+use self::import::ImportedTest;
+use crate::{
+    lessons::LessonId,
+    modes::ConfidenceMode,
+    stats::{
+        keys::{merge_key_stats, KeyStats},
+        scoring::Scoring,
+        TestStats,
+    },
+};
+
+pub mod export;
+pub mod import;
+
+/// Represents the results of a completed typing test, along with the modes that affect how it
+/// can be compared with other results.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestResult {
+    pub stats: TestStats,
+    pub confidence_mode: ConfidenceMode,
+
+    /// The name of the keyboard layout that was emulated during the test, if any.
+    pub emulated_layout: Option<String>,
+
+    /// The strategy used to score the test.
+    pub scoring: Scoring,
+
+    /// The time at which the test was completed.
+    pub completed_at: SystemTime,
+
+    /// Where the result was imported from, if it wasn't recorded locally.
+    pub imported: Option<ImportedTest>,
+
+    /// Whether the test was a practice drill, which doesn't count towards personal bests.
+    pub practice: bool,
+
+    /// The lesson the test was typed for, if any. Lessons don't count towards personal bests.
+    pub lesson: Option<LessonId>,
+}
+
+/// Stores the results of previous typing tests.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct History {
+    results: Vec<TestResult>,
+}
+
+impl TestResult {
+    /// Creates a new test result.
+    pub fn new(stats: TestStats, confidence_mode: ConfidenceMode) -> Self {
+        Self {
+            stats,
+            confidence_mode,
+            emulated_layout: None,
+            scoring: Scoring::default(),
+            completed_at: SystemTime::now(),
+            imported: None,
+            practice: false,
+            lesson: None,
+        }
+    }
+
+    /// Records the keyboard layout that was emulated during the test.
+    pub fn with_emulated_layout(mut self, emulated_layout: Option<String>) -> Self {
+        self.emulated_layout = emulated_layout;
+        self
+    }
+
+    /// Records the strategy used to score the test.
+    pub fn with_scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Records where the result was imported from.
+    pub fn with_import(mut self, imported: ImportedTest) -> Self {
+        self.imported = Some(imported);
+        self
+    }
+
+    /// Records whether the test was a practice drill.
+    pub fn with_practice(mut self, practice: bool) -> Self {
+        self.practice = practice;
+        self
+    }
+
+    /// Records the lesson the test was typed for.
+    pub fn with_lesson(mut self, lesson: Option<LessonId>) -> Self {
+        self.lesson = lesson;
+        self
+    }
+
+    /// Calculates the WPM for this result, using its scoring method.
+    pub fn wpm(&self) -> Option<u64> {
+        self.stats
+            .get_latest_checkpoint()
+            .and_then(|checkpoint| self.scoring.wpm(checkpoint))
+    }
+
+    /// Calculates the accuracy for this result, using its scoring method.
+    pub fn accuracy(&self) -> Option<f32> {
+        self.stats
+            .get_latest_checkpoint()
+            .and_then(|checkpoint| self.scoring.accuracy(&self.stats, checkpoint))
+    }
+}
+
+impl History {
+    /// Creates a new, empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the results in this history (in the order they were added, or completed for imported
+    /// results).
+    pub fn results(&self) -> &[TestResult] {
+        &self.results
+    }
+
+    /// Adds a test result to this history.
+    pub fn push(&mut self, result: TestResult) {
+        self.results.push(result);
+    }
+
+    /// Adds imported results to this history, then sorts all the results by when they were
+    /// completed.
+    pub fn import(&mut self, results: impl IntoIterator<Item = TestResult>) {
+        self.results.extend(results);
+        self.results.sort_by_key(|result| result.completed_at);
+    }
+
+    /// Gets the result with the highest WPM, which is the personal best.
+    ///
+    /// Practice drills, lessons and failed tests are excluded.
+    pub fn personal_best(&self) -> Option<&TestResult> {
+        self.results
+            .iter()
+            .filter(|result| {
+                !result.practice
+                    && result.lesson.is_none()
+                    && result.stats.get_fail_reason().is_none()
+            })
+            .filter_map(|result| result.wpm().map(|wpm| (wpm, result)))
+            .max_by_key(|(wpm, _)| *wpm)
+            .map(|(_, result)| result)
+    }
+
+    /// Calculates the statistics for each key, aggregated across all results.
+    pub fn key_stats(&self) -> HashMap<char, KeyStats> {
+        let key_stats: Vec<_> = self.results.iter().map(|r| r.stats.key_stats()).collect();
+        merge_key_stats(&key_stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(words: &[(&str, &str)], practice: bool) -> TestResult {
+        let mut stats = TestStats::new();
+        for (expected, actual) in words {
+            stats.submit_word(expected, actual);
+        }
+        stats.finish();
+
+        TestResult::new(stats, ConfidenceMode::Off).with_practice(practice)
+    }
+
+    #[test]
+    fn personal_best_excludes_practice_drills() {
+        let mut history = History::new();
+        history.push(result(&[("cat", "cat")], false));
+        history.push(result(&[("cat", "cat"), ("dog", "dog")], true));
+
+        let best = history.personal_best().unwrap();
+
+        assert!(!best.practice);
+        assert_eq!(1, best.stats.get_latest_checkpoint().unwrap().correct_words);
+    }
+
+    #[test]
+    fn personal_best_is_undefined_without_counted_results() {
+        let mut history = History::new();
+        history.push(result(&[("cat", "cat")], true));
+
+        assert!(history.personal_best().is_none());
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    #[test]
+    fn result_survives_json_round_trip() {
+        let result = result(&[("cat", "cat"), ("dog", "dig")], false)
+            .with_emulated_layout(Some(String::from("Dvorak")))
+            .with_practice(true);
+
+        let json = serde_json::to_string(&result).unwrap();
+        let parsed: TestResult = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json, serde_json::to_string(&parsed).unwrap());
+        assert_eq!(result.completed_at, parsed.completed_at);
+        assert_eq!(Some("Dvorak"), parsed.emulated_layout.as_deref());
+        assert!(parsed.practice);
+        assert_eq!(
+            result.stats.get_missed_words(),
+            parsed.stats.get_missed_words()
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use std::time::UNIX_EPOCH;
+
+use crate::{
+    lessons::LessonId,
+    modes::{ConfidenceMode, FailReason},
+    stats::{scoring::Scoring, MissedWord, TestCheckpoint},
+};
+
+use super::{import::ImportedTest, TestResult};
+
+/// The column names used when exporting results to CSV.
+pub const CSV_HEADERS: [&str; 18] = [
+    "completed_at",
+    "wpm",
+    "raw_wpm",
+    "accuracy",
+    "correct_chars",
+    "incorrect_chars",
+    "correct_words",
+    "incorrect_words",
+    "missed_words",
+    "duration_seconds",
+    "consistency",
+    "confidence_mode",
+    "emulated_layout",
+    "scoring",
+    "fail_reason",
+    "imported_from",
+    "practice",
+    "lesson",
+];
+
+/// Represents the summary of a test result that is exported.
+///
+/// The keystrokes and replay are left out, since they are only used within the application and
+/// would make up most of an export.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ExportedResult {
+    /// The time at which the test was completed, in seconds since the Unix epoch.
+    pub completed_at: Option<u64>,
+
+    /// The WPM and accuracy, using the scoring method of the result.
+    pub wpm: Option<u64>,
+    pub raw_wpm: Option<u64>,
+    pub accuracy: Option<f32>,
+
+    pub correct_chars: Option<u64>,
+    pub incorrect_chars: Option<u64>,
+    pub correct_words: Option<u64>,
+    pub incorrect_words: Option<u64>,
+    pub missed_words: Vec<MissedWord>,
+    pub duration_seconds: Option<f32>,
+    pub consistency: Option<f32>,
+
+    pub confidence_mode: ConfidenceMode,
+    pub emulated_layout: Option<String>,
+    pub scoring: Scoring,
+    pub fail_reason: Option<FailReason>,
+    pub imported: Option<ImportedTest>,
+    pub practice: bool,
+    pub lesson: Option<LessonId>,
+}
+
+impl From<&TestResult> for ExportedResult {
+    fn from(result: &TestResult) -> Self {
+        let stats = &result.stats;
+        let checkpoint = stats.get_latest_checkpoint();
+        let count = |f: fn(&TestCheckpoint) -> u64| checkpoint.map(f);
+
+        Self {
+            completed_at: result
+                .completed_at
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs()),
+            wpm: checkpoint.and_then(|cp| result.scoring.wpm(cp)),
+            raw_wpm: checkpoint.and_then(|cp| cp.raw_wpm()),
+            accuracy: checkpoint.and_then(|cp| result.scoring.accuracy(stats, cp)),
+            correct_chars: count(|cp| cp.correct_chars),
+            incorrect_chars: count(|cp| cp.incorrect_chars),
+            correct_words: count(|cp| cp.correct_words),
+            incorrect_words: count(|cp| cp.incorrect_words),
+            missed_words: stats.get_missed_words().to_vec(),
+            duration_seconds: checkpoint.map(|cp| cp.elapsed.as_secs_f32()),
+            consistency: stats.consistency(),
+            confidence_mode: result.confidence_mode,
+            emulated_layout: result.emulated_layout.clone(),
+            scoring: result.scoring,
+            fail_reason: stats.get_fail_reason(),
+            imported: result.imported.clone(),
+            practice: result.practice,
+            lesson: result.lesson.clone(),
+        }
+    }
+}
+
+/// Converts a sequence of test results to CSV, with one row per result.
+///
+/// The WPM and accuracy use the scoring method of each result. Statistics that are undefined
+/// (e.g. for an empty test) are left blank, and timestamps are in seconds since the Unix epoch.
+pub fn to_csv<'a>(results: impl IntoIterator<Item = &'a TestResult>) -> String {
+    let mut csv = CSV_HEADERS.join(",");
+    csv.push('\n');
+
+    for result in results {
+        let row: Vec<String> = csv_row(&ExportedResult::from(result))
+            .iter()
+            .map(|f| escape(f))
+            .collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Converts a sequence of test results to a JSON list of [`ExportedResult`]s.
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+pub fn to_json<'a>(
+    results: impl IntoIterator<Item = &'a TestResult>,
+) -> serde_json::Result<String> {
+    let exported: Vec<_> = results.into_iter().map(ExportedResult::from).collect();
+    serde_json::to_string_pretty(&exported)
+}
+
+/// Gets the fields for a single result, in the same order as [`CSV_HEADERS`].
+fn csv_row(result: &ExportedResult) -> Vec<String> {
+    let optional = |value: Option<String>| value.unwrap_or_default();
+    let count = |value: Option<u64>| optional(value.map(|v| v.to_string()));
+
+    vec![
+        count(result.completed_at),
+        count(result.wpm),
+        count(result.raw_wpm),
+        optional(result.accuracy.map(|a| format!("{:.2}", a))),
+        count(result.correct_chars),
+        count(result.incorrect_chars),
+        count(result.correct_words),
+        count(result.incorrect_words),
+        result.missed_words.len().to_string(),
+        optional(result.duration_seconds.map(|d| format!("{:.2}", d))),
+        optional(result.consistency.map(|c| format!("{:.2}", c))),
+        result.confidence_mode.to_string(),
+        optional(result.emulated_layout.clone()),
+        result.scoring.to_string(),
+        optional(result.fail_reason.map(|r| r.to_string())),
+        optional(result.imported.as_ref().map(|i| i.source.to_string())),
+        result.practice.to_string(),
+        optional(
+            result
+                .lesson
+                .as_ref()
+                .map(|l| format!("{} {}", l.curriculum, l.index + 1)),
+        ),
+    ]
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote or line break.
+fn escape(field: &str) -> String {
+    if field.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use rstest::*;
+
+    use crate::{modes::ConfidenceMode, stats::TestStats};
+
+    use super::*;
+
+    #[rstest(
+        field,
+        expected,
+        case("plain", "plain"),
+        case("a, b", "\"a, b\""),
+        case("say \"hi\"", "\"say \"\"hi\"\"\""),
+        case("two\nlines", "\"two\nlines\"")
+    )]
+    fn escape_quotes_special_fields(field: &str, expected: &str) {
+        assert_eq!(expected, escape(field));
+    }
+
+    #[test]
+    fn to_csv_writes_one_row_per_result() {
+        let mut stats = TestStats::new();
+        stats.submit_word("cat", "cat");
+        stats.submit_word("dog", "dig");
+        stats.finish();
+
+        let mut result = TestResult::new(stats, ConfidenceMode::Off);
+        result.completed_at = UNIX_EPOCH + Duration::from_secs(1234);
+
+        let csv = to_csv(&[result.clone(), result]);
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(3, lines.len());
+        assert_eq!(CSV_HEADERS.join(","), lines[0]);
+        assert!(lines[1].starts_with("1234,"));
+        assert!(lines[1].contains(",\"Standard WPM, Characters Accuracy\","));
+        assert_eq!(lines[1], lines[2]);
+    }
+
+    #[test]
+    fn to_csv_leaves_undefined_stats_blank() {
+        let result = TestResult::new(TestStats::new(), ConfidenceMode::Off);
+        let csv = to_csv(&[result]);
+        let fields: Vec<_> = csv.lines().nth(1).unwrap().split(',').collect();
+
+        assert_eq!("", fields[1]);
+        assert_eq!("", fields[3]);
+        assert_eq!("0", fields[8]);
+    }
+
+    #[test]
+    fn to_csv_writes_lesson() {
+        let result =
+            TestResult::new(TestStats::new(), ConfidenceMode::Off).with_lesson(Some(LessonId {
+                curriculum: String::from("Home Row"),
+                index: 2,
+            }));
+        let csv = to_csv(&[result]);
+
+        assert!(csv.lines().nth(1).unwrap().ends_with(",false,Home Row 3"));
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    #[test]
+    fn to_json_leaves_out_keystrokes_and_replay() {
+        let mut stats = TestStats::new();
+        stats.record_input("cat", "", "cat");
+        stats.submit_word("cat", "cat");
+        stats.finish();
+
+        let result = TestResult::new(stats, ConfidenceMode::Off);
+        let json = to_json(&[result]).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let exported = value[0].as_object().unwrap();
+
+        assert!(exported.contains_key("wpm"));
+        assert!(exported.contains_key("lesson"));
+        assert!(!json.contains("keystrokes"));
+        assert!(!json.contains("replay"));
+    }
+}
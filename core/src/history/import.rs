@@ -0,0 +1,707 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Display,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    modes::ConfidenceMode,
+    stats::{TestCheckpoint, TestStats},
+};
+
+use super::TestResult;
+
+/// Represents another typing test site that results can be imported from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImportSource {
+    /// Results exported from the account page on monkeytype.
+    Monkeytype,
+
+    /// Results exported from typetest.io.
+    TypetestIo,
+}
+
+/// Represents where an imported result came from, along with the test mode it was taken in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImportedTest {
+    pub source: ImportSource,
+
+    /// A description of the test mode on the original site (e.g. "time 60"), if it was exported.
+    pub mode: Option<String>,
+}
+
+/// Represents the outcome of importing results, including any records that were skipped.
+#[derive(Clone, Debug, Default)]
+pub struct ImportReport {
+    pub results: Vec<TestResult>,
+    pub skipped: Vec<SkippedRecord>,
+}
+
+/// Represents a record in an export that couldn't be imported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkippedRecord {
+    /// The position of the record in the export, starting from 1 (excluding any header row).
+    pub record: usize,
+    pub reason: SkipReason,
+}
+
+/// Represents the reason that a record was skipped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SkipReason {
+    /// A required field was missing or empty.
+    MissingField(&'static str),
+
+    /// A field couldn't be understood.
+    InvalidField { field: &'static str, value: String },
+
+    /// The record was for a test that took no time, so no statistics can be calculated.
+    ZeroDuration,
+}
+
+/// Represents an error that prevented an export from being imported at all.
+#[derive(Debug)]
+pub enum ImportError {
+    /// The export didn't contain any records.
+    Empty,
+
+    /// The export doesn't have a column that every record needs.
+    MissingColumn(&'static str),
+
+    /// The export wasn't valid JSON, or didn't contain a list of results.
+    #[cfg(feature = "serde_json")]
+    InvalidJson(String),
+}
+
+/// Represents the names that each field can have in an export, after normalisation.
+struct Columns {
+    wpm: &'static [&'static str],
+    accuracy: &'static [&'static str],
+    duration: &'static [&'static str],
+    timestamp: &'static [&'static str],
+    mode: &'static [&'static str],
+    mode_detail: &'static [&'static str],
+}
+
+/// The longest test duration that is imported, in seconds. Longer durations can't be from a real
+/// test, and could overflow when converted.
+const MAX_DURATION_SECONDS: f64 = 24.0 * 60.0 * 60.0;
+
+/// The largest number of correct or incorrect characters that an imported test can have, which
+/// keeps the totals well within the range of the character counts.
+const MAX_CHARS: f64 = u32::MAX as f64;
+
+const MONKEYTYPE_COLUMNS: Columns = Columns {
+    wpm: &["wpm"],
+    accuracy: &["acc"],
+    duration: &["testduration"],
+    timestamp: &["timestamp"],
+    mode: &["mode"],
+    mode_detail: &["mode2"],
+};
+
+const TYPETEST_IO_COLUMNS: Columns = Columns {
+    wpm: &["wpm", "netwpm", "speed"],
+    accuracy: &["accuracy", "acc"],
+    duration: &["duration", "time", "seconds", "testduration"],
+    timestamp: &["date", "timestamp", "createdat"],
+    mode: &["mode", "test", "testtype"],
+    mode_detail: &["length", "words", "modeoption"],
+};
+
+impl ImportSource {
+    /// An array of all the import sources.
+    pub const ALL_SOURCES: [ImportSource; 2] = [ImportSource::Monkeytype, ImportSource::TypetestIo];
+
+    fn columns(&self) -> &'static Columns {
+        match self {
+            ImportSource::Monkeytype => &MONKEYTYPE_COLUMNS,
+            ImportSource::TypetestIo => &TYPETEST_IO_COLUMNS,
+        }
+    }
+}
+
+/// Imports the results from a CSV export.
+///
+/// Column names are matched case-insensitively, ignoring spaces and punctuation. Records with
+/// missing or invalid fields are skipped and listed in the report.
+pub fn import_csv(source: ImportSource, contents: &str) -> Result<ImportReport, ImportError> {
+    let mut rows = parse_csv(contents).into_iter();
+    let headers: Vec<String> = match rows.next() {
+        Some(headers) => headers.iter().map(|h| normalise(h)).collect(),
+        None => return Err(ImportError::Empty),
+    };
+
+    let columns = source.columns();
+    for (name, aliases) in [("wpm", columns.wpm), ("duration", columns.duration)].iter() {
+        if !headers.iter().any(|h| aliases.contains(&h.as_str())) {
+            return Err(ImportError::MissingColumn(name));
+        }
+    }
+
+    let records = rows
+        .filter(|row| row.iter().any(|field| !field.is_empty()))
+        .map(|row| headers.iter().cloned().zip(row).collect());
+
+    import_records(source, records)
+}
+
+/// Imports the results from a JSON export, which is either a list of results or an object with
+/// the list under `data` or `results`.
+#[cfg(feature = "serde_json")]
+pub fn import_json(source: ImportSource, contents: &str) -> Result<ImportReport, ImportError> {
+    use serde_json::Value;
+
+    let value: Value =
+        serde_json::from_str(contents).map_err(|e| ImportError::InvalidJson(e.to_string()))?;
+
+    let records = match value {
+        Value::Array(records) => records,
+        Value::Object(mut object) => {
+            match object.remove("data").or_else(|| object.remove("results")) {
+                Some(Value::Array(records)) => records,
+                _ => {
+                    return Err(ImportError::InvalidJson(String::from(
+                        "No list of results was found",
+                    )))
+                }
+            }
+        }
+        _ => {
+            return Err(ImportError::InvalidJson(String::from(
+                "No list of results was found",
+            )))
+        }
+    };
+
+    let records = records.into_iter().map(|record| match record {
+        Value::Object(fields) => fields
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let value = match value {
+                    Value::String(s) => s,
+                    Value::Number(n) => n.to_string(),
+                    Value::Bool(b) => b.to_string(),
+                    _ => return None,
+                };
+
+                Some((normalise(&key), value))
+            })
+            .collect(),
+        _ => HashMap::new(),
+    });
+
+    import_records(source, records)
+}
+
+/// Converts normalised records into test results.
+fn import_records(
+    source: ImportSource,
+    records: impl Iterator<Item = HashMap<String, String>>,
+) -> Result<ImportReport, ImportError> {
+    let mut report = ImportReport::default();
+    let mut empty = true;
+
+    for (i, record) in records.enumerate() {
+        empty = false;
+        match import_record(source, &record) {
+            Ok(result) => report.results.push(result),
+            Err(reason) => report.skipped.push(SkippedRecord {
+                record: i + 1,
+                reason,
+            }),
+        }
+    }
+
+    if empty {
+        Err(ImportError::Empty)
+    } else {
+        Ok(report)
+    }
+}
+
+/// Converts a single record into a test result.
+///
+/// Only the totals of a test are exported, so the character counts are chosen so that the
+/// standard WPM and character accuracy match the exported WPM and accuracy.
+fn import_record(
+    source: ImportSource,
+    record: &HashMap<String, String>,
+) -> Result<TestResult, SkipReason> {
+    let columns = source.columns();
+    let field = |aliases: &[&str]| {
+        aliases
+            .iter()
+            .filter_map(|alias| record.get(*alias))
+            .map(|value| value.trim())
+            .find(|value| !value.is_empty())
+    };
+
+    let wpm = parse_number("wpm", field(columns.wpm))?;
+    let seconds = parse_number("duration", field(columns.duration))?;
+    let accuracy = match field(columns.accuracy) {
+        Some(value) => parse_number("accuracy", Some(value.trim_end_matches('%')))?,
+        None => 100.0,
+    };
+
+    let completed_at = field(columns.timestamp)
+        .ok_or(SkipReason::MissingField("timestamp"))
+        .and_then(|value| {
+            parse_timestamp(value).ok_or_else(|| SkipReason::InvalidField {
+                field: "timestamp",
+                value: value.to_string(),
+            })
+        })?;
+
+    if seconds <= 0.0 {
+        return Err(SkipReason::ZeroDuration);
+    }
+
+    if seconds > MAX_DURATION_SECONDS {
+        return Err(SkipReason::InvalidField {
+            field: "duration",
+            value: seconds.to_string(),
+        });
+    }
+
+    if !(0.0..=100.0).contains(&accuracy) {
+        return Err(SkipReason::InvalidField {
+            field: "accuracy",
+            value: accuracy.to_string(),
+        });
+    }
+
+    let correct_chars = (wpm * 5.0 * seconds / 60.0).round();
+    let incorrect_chars = if accuracy > 0.0 {
+        (correct_chars * (100.0 - accuracy) / accuracy).round()
+    } else if correct_chars == 0.0 {
+        0.0
+    } else {
+        // No accuracy can be matched when there are correct characters
+        return Err(SkipReason::InvalidField {
+            field: "accuracy",
+            value: accuracy.to_string(),
+        });
+    };
+
+    if correct_chars > MAX_CHARS {
+        return Err(SkipReason::InvalidField {
+            field: "wpm",
+            value: wpm.to_string(),
+        });
+    }
+
+    if incorrect_chars > MAX_CHARS {
+        return Err(SkipReason::InvalidField {
+            field: "accuracy",
+            value: accuracy.to_string(),
+        });
+    }
+
+    let checkpoint = TestCheckpoint {
+        elapsed: Duration::from_secs_f64(seconds),
+        correct_chars: correct_chars as u64,
+        incorrect_chars: incorrect_chars as u64,
+        correct_words: 0,
+        incorrect_words: 0,
+        blocked_attempts: 0,
+    };
+
+    let mode = match (field(columns.mode), field(columns.mode_detail)) {
+        (Some(mode), Some(detail)) => Some(format!("{} {}", mode, detail)),
+        (Some(mode), None) => Some(mode.to_string()),
+        (None, _) => None,
+    };
+
+    let mut result = TestResult::new(TestStats::from_checkpoint(checkpoint), ConfidenceMode::Off)
+        .with_import(ImportedTest { source, mode });
+    result.completed_at = completed_at;
+
+    Ok(result)
+}
+
+/// Parses a required, non-negative number.
+fn parse_number(field: &'static str, value: Option<&str>) -> Result<f64, SkipReason> {
+    let value = value.ok_or(SkipReason::MissingField(field))?;
+    match value.parse::<f64>() {
+        Ok(number) if number.is_finite() && number >= 0.0 => Ok(number),
+        _ => Err(SkipReason::InvalidField {
+            field,
+            value: value.to_string(),
+        }),
+    }
+}
+
+/// Parses a Unix timestamp (in seconds or milliseconds) or an ISO 8601 date, which is assumed to
+/// be in UTC unless it has an offset.
+///
+/// Returns `None` if the timestamp is invalid or can't be represented.
+fn parse_timestamp(value: &str) -> Option<SystemTime> {
+    if let Ok(number) = value.parse::<f64>() {
+        // Millisecond timestamps have been larger than this since 1973
+        let seconds = if number >= 1e11 {
+            number / 1000.0
+        } else {
+            number
+        };
+        return if seconds.is_finite() && seconds >= 0.0 && seconds < u64::MAX as f64 {
+            UNIX_EPOCH.checked_add(Duration::from_secs_f64(seconds))
+        } else {
+            None
+        };
+    }
+
+    let (date, time) = match value.find(|c| c == 'T' || c == ' ') {
+        Some(i) => (&value[..i], &value[i + 1..]),
+        None => (value, ""),
+    };
+
+    let mut date_parts = date.split('-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (
+        date_parts.next()??,
+        date_parts.next()??,
+        date_parts.next()??,
+    );
+    if date_parts.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // The time zone is either `Z` or an offset from UTC, which comes after any fractional seconds
+    let (time, offset) = match time.find(|c| c == 'Z' || c == '+' || c == '-') {
+        Some(i) => (&time[..i], parse_offset(&time[i..])?),
+        None => (time, 0),
+    };
+
+    // Ignore fractional seconds
+    let time = time.split('.').next().unwrap_or_default().trim();
+    let mut time_parts = time
+        .split(':')
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<i64>().ok());
+    let hours = time_parts.next().unwrap_or(Some(0))?;
+    let minutes = time_parts.next().unwrap_or(Some(0))?;
+    let seconds = time_parts.next().unwrap_or(Some(0))?;
+    if !(0..24).contains(&hours) || !(0..60).contains(&minutes) || !(0..=60).contains(&seconds) {
+        return None;
+    }
+
+    let seconds = days_from_civil(year, month, day)?
+        .checked_mul(86400)?
+        .checked_add(hours * 3600 + minutes * 60 + seconds)?
+        .checked_sub(offset)?;
+    if seconds >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(seconds as u64))
+    } else {
+        None
+    }
+}
+
+/// Parses an ISO 8601 time zone (`Z`, `±hh:mm`, `±hhmm` or `±hh`), returning its offset from UTC
+/// in seconds.
+fn parse_offset(zone: &str) -> Option<i64> {
+    let sign = match zone.chars().next()? {
+        'Z' if zone.len() == 1 => return Some(0),
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+
+    let digits: String = zone[1..].chars().filter(|&c| c != ':').collect();
+    if !(digits.len() == 2 || digits.len() == 4) || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let hours: i64 = digits[..2].parse().ok()?;
+    let minutes: i64 = digits[2..].parse().unwrap_or(0);
+    if hours >= 24 || minutes >= 60 {
+        return None;
+    }
+
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Calculates the number of days between the Unix epoch and a date in the Gregorian calendar.
+///
+/// Returns `None` if the number of days overflows.
+fn days_from_civil(year: i64, month: i64, day: i64) -> Option<i64> {
+    let year = if month <= 2 {
+        year.checked_sub(1)?
+    } else {
+        year
+    };
+    let era = (if year >= 0 {
+        year
+    } else {
+        year.checked_sub(399)?
+    }) / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era.checked_mul(146097)?
+        .checked_add(day_of_era)?
+        .checked_sub(719468)
+}
+
+/// Normalises a column name, so that names only differing by case or punctuation are equal.
+fn normalise(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Splits CSV text into rows of fields, handling quoted fields.
+fn parse_csv(contents: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = contents.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
+}
+
+impl Display for ImportSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ImportSource::Monkeytype => f.write_str("Monkeytype"),
+            ImportSource::TypetestIo => f.write_str("typetest.io"),
+        }
+    }
+}
+
+impl Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::MissingField(field) => write!(f, "Missing {}", field),
+            SkipReason::InvalidField { field, value } => {
+                write!(f, "Invalid {} \"{}\"", field, value)
+            }
+            SkipReason::ZeroDuration => f.write_str("Test has no duration"),
+        }
+    }
+}
+
+impl Display for SkippedRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Record {}: {}", self.record, self.reason)
+    }
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Empty => f.write_str("The export doesn't contain any results"),
+            ImportError::MissingColumn(column) => {
+                write!(f, "The export doesn't have a {} column", column)
+            }
+            #[cfg(feature = "serde_json")]
+            ImportError::InvalidJson(e) => write!(f, "Invalid JSON: {}", e),
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    const MONKEYTYPE_CSV: &str = "\
+_id,isPb,wpm,acc,rawWpm,consistency,charStats,mode,mode2,quoteLength,restartCount,testDuration,afkDuration,incompleteTestSeconds,punctuation,numbers,language,funbox,difficulty,lazyMode,blindMode,bailedOut,tags,timestamp
+abc,true,60,96,62.5,80.1,300;12;0;0,time,30,-1,0,30,0,0,false,false,english,none,normal,false,false,false,\"a,b\",1609459200000
+def,false,,96,62.5,80.1,300;12;0;0,time,30,-1,0,30,0,0,false,false,english,none,normal,false,false,false,,1609459200000
+ghi,false,50,100,50,80.1,250;0;0;0,words,25,-1,0,0,0,0,false,false,english,none,normal,false,false,false,,1609459200000
+";
+
+    #[test]
+    fn import_csv_maps_monkeytype_fields() {
+        let report = import_csv(ImportSource::Monkeytype, MONKEYTYPE_CSV).unwrap();
+        assert_eq!(1, report.results.len());
+
+        let result = &report.results[0];
+        let checkpoint = result.stats.get_latest_checkpoint().unwrap();
+
+        assert_eq!(Some(60), checkpoint.effective_wpm());
+        assert!((96.0 - checkpoint.accuracy().unwrap()).abs() < 0.5);
+        assert_eq!(Duration::from_secs(30), checkpoint.elapsed);
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_secs(1609459200),
+            result.completed_at
+        );
+        assert_eq!(
+            Some(ImportedTest {
+                source: ImportSource::Monkeytype,
+                mode: Some(String::from("time 30")),
+            }),
+            result.imported
+        );
+    }
+
+    #[test]
+    fn import_csv_reports_skipped_records() {
+        let report = import_csv(ImportSource::Monkeytype, MONKEYTYPE_CSV).unwrap();
+
+        assert_eq!(
+            vec![
+                SkippedRecord {
+                    record: 2,
+                    reason: SkipReason::MissingField("wpm"),
+                },
+                SkippedRecord {
+                    record: 3,
+                    reason: SkipReason::ZeroDuration,
+                },
+            ],
+            report.skipped
+        );
+    }
+
+    #[test]
+    fn import_csv_requires_columns() {
+        let result = import_csv(ImportSource::TypetestIo, "Date,Accuracy\n2021-01-01,95\n");
+        assert!(matches!(result, Err(ImportError::MissingColumn("wpm"))));
+    }
+
+    #[test]
+    fn import_csv_matches_typetest_io_columns() {
+        let csv = "Date,Net WPM,Accuracy,Duration,Mode\n2021-01-01 12:30:00,80,95.5%,60,1 minute\n";
+        let report = import_csv(ImportSource::TypetestIo, csv).unwrap();
+
+        assert!(report.skipped.is_empty());
+        assert_eq!(
+            Some(80),
+            report.results[0]
+                .stats
+                .get_latest_checkpoint()
+                .unwrap()
+                .effective_wpm()
+        );
+    }
+
+    #[rstest(
+        value,
+        expected_seconds,
+        case("1609459200", Some(1609459200)),
+        case("1609459200000", Some(1609459200)),
+        case("2021-01-01", Some(1609459200)),
+        case("2021-01-01T12:30:15.250Z", Some(1609504215)),
+        case("2021-03-04T12:00:00-05:00", Some(1614877200)),
+        case("2021-03-04T12:00:00+02:00", Some(1614852000)),
+        case("2021-03-04T12:00:00.5+0200", Some(1614852000)),
+        case("2021-03-04 12:00:00 +02", Some(1614852000)),
+        case("2021-03-04T12:00:00+25:00", None),
+        case("2021-03-04T12:00:00Zulu", None),
+        case("1969-12-31", None),
+        case("2021-13-01", None),
+        case("2021-01-01T25:00:00", None),
+        case("yesterday", None),
+        case("inf", None),
+        case("NaN", None),
+        case("1e300", None),
+        case("9223372036854775807-01-01", None),
+        case("99999999999999999-12-31", None)
+    )]
+    fn parse_timestamp_formats(value: &str, expected_seconds: Option<u64>) {
+        let expected = expected_seconds.map(|s| UNIX_EPOCH + Duration::from_secs(s));
+        assert_eq!(expected, parse_timestamp(value));
+    }
+
+    #[rstest(
+        wpm,
+        accuracy,
+        duration,
+        date,
+        field,
+        case("60", "95", "1e300", "2021-01-01", "duration"),
+        case("60", "95", "inf", "2021-01-01", "duration"),
+        case("1e300", "95", "60", "2021-01-01", "wpm"),
+        case("60", "1e-300", "60", "2021-01-01", "accuracy"),
+        case("60", "0", "60", "2021-01-01", "accuracy"),
+        case("60", "95", "60", "inf", "timestamp"),
+        case("60", "95", "60", "1e300", "timestamp"),
+        case("60", "95", "60", "99999999999999999-12-31", "timestamp")
+    )]
+    fn import_csv_skips_out_of_range_values(
+        wpm: &str,
+        accuracy: &str,
+        duration: &str,
+        date: &str,
+        field: &str,
+    ) {
+        let csv = format!(
+            "Date,WPM,Accuracy,Duration\n{},{},{},{}\n",
+            date, wpm, accuracy, duration
+        );
+        let report = import_csv(ImportSource::TypetestIo, &csv).unwrap();
+
+        assert!(report.results.is_empty());
+        assert!(
+            matches!(&report.skipped[0].reason, SkipReason::InvalidField { field: f, .. } if *f == field),
+            "{:?}",
+            report.skipped
+        );
+    }
+
+    #[test]
+    fn parse_csv_handles_quotes() {
+        let rows = parse_csv("a,\"b,c\",\"say \"\"hi\"\"\"\r\n\"two\nlines\",\n");
+        assert_eq!(
+            vec![vec!["a", "b,c", "say \"hi\""], vec!["two\nlines", ""],],
+            rows
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn import_json_reads_monkeytype_results() {
+        let json = r#"{"data": [
+            {"wpm": 72.4, "acc": 98, "testDuration": 15.5, "timestamp": 1609459200000, "mode": "time", "mode2": "15", "charStats": [90, 2, 0, 0]},
+            {"wpm": "fast", "acc": 98, "testDuration": 15, "timestamp": 1609459200000}
+        ]}"#;
+
+        let report = import_json(ImportSource::Monkeytype, json).unwrap();
+        assert_eq!(1, report.results.len());
+        assert_eq!(
+            vec![SkippedRecord {
+                record: 2,
+                reason: SkipReason::InvalidField {
+                    field: "wpm",
+                    value: String::from("fast"),
+                },
+            }],
+            report.skipped
+        );
+    }
+}
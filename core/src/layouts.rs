@@ -0,0 +1,596 @@
+use std::{collections::HashMap, fmt::Display, fs, io, path::Path};
+
+/// The maximum number of keys in each row of a layout.
+pub const MAX_ROW_KEYS: usize = 13;
+
+/// The fingers used by each column in the number row, which is offset from the other rows.
+const NUMBER_ROW_FINGERS: [Finger; MAX_ROW_KEYS] = [
+    Finger::LeftPinky,
+    Finger::LeftPinky,
+    Finger::LeftRing,
+    Finger::LeftMiddle,
+    Finger::LeftIndex,
+    Finger::LeftIndex,
+    Finger::RightIndex,
+    Finger::RightIndex,
+    Finger::RightMiddle,
+    Finger::RightRing,
+    Finger::RightPinky,
+    Finger::RightPinky,
+    Finger::RightPinky,
+];
+
+/// The fingers used by each column in the letter rows.
+const LETTER_ROW_FINGERS: [Finger; MAX_ROW_KEYS] = [
+    Finger::LeftPinky,
+    Finger::LeftRing,
+    Finger::LeftMiddle,
+    Finger::LeftIndex,
+    Finger::LeftIndex,
+    Finger::RightIndex,
+    Finger::RightIndex,
+    Finger::RightMiddle,
+    Finger::RightRing,
+    Finger::RightPinky,
+    Finger::RightPinky,
+    Finger::RightPinky,
+    Finger::RightPinky,
+];
+
+/// Represents a hand used for typing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+/// Represents a finger used for typing, assuming standard touch typing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Finger {
+    LeftPinky,
+    LeftRing,
+    LeftMiddle,
+    LeftIndex,
+    Thumb,
+    RightIndex,
+    RightMiddle,
+    RightRing,
+    RightPinky,
+}
+
+/// Represents a row of keys on a keyboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Row {
+    Number,
+    Top,
+    Home,
+    Bottom,
+    Thumb,
+}
+
+/// Represents the physical position of a key, independent of the layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyPosition {
+    pub row: Row,
+    pub column: usize,
+}
+
+/// Represents the characters produced by a key in a layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LayoutKey {
+    pub unshifted: char,
+    pub shifted: Option<char>,
+}
+
+/// Represents where a character is typed in a layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyInfo {
+    pub position: KeyPosition,
+    pub finger: Finger,
+
+    /// Whether Shift needs to be held to type the character.
+    pub shifted: bool,
+}
+
+/// Represents a keyboard layout, which maps characters to physical keys.
+///
+/// Layouts only cover the character keys in the number, top, home and bottom rows. The space bar
+/// is shared by every layout, and is the only key in the thumb row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyboardLayout {
+    name: String,
+    rows: [Vec<LayoutKey>; 4],
+    keys: HashMap<char, KeyInfo>,
+}
+
+/// Emulates a target layout while typing on a different physical layout.
+///
+/// Characters are remapped to the character at the same physical position in the target layout,
+/// so typing on a QWERTY keyboard can produce Colemak text without changing the OS layout.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutEmulation {
+    name: String,
+    mapping: HashMap<char, char>,
+}
+
+/// Represents the keyboard layouts that are built into the application.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuiltinLayout {
+    Qwerty,
+    Azerty,
+    Qwertz,
+    Dvorak,
+    Colemak,
+    ColemakDh,
+    Workman,
+}
+
+/// Represents an error encountered while loading a keyboard layout.
+#[derive(Debug)]
+pub enum LayoutError {
+    Io(io::Error),
+    MissingName,
+    MissingRow(Row),
+    UnknownField { line: usize, field: String },
+    InvalidKey { line: usize, key: String },
+    DuplicateChar { line: usize, c: char },
+    TooManyKeys { line: usize },
+}
+
+impl Finger {
+    /// An array of all the fingers, from left to right.
+    pub const ALL_FINGERS: [Finger; 9] = [
+        Finger::LeftPinky,
+        Finger::LeftRing,
+        Finger::LeftMiddle,
+        Finger::LeftIndex,
+        Finger::Thumb,
+        Finger::RightIndex,
+        Finger::RightMiddle,
+        Finger::RightRing,
+        Finger::RightPinky,
+    ];
+
+    /// Gets the hand that this finger belongs to, or `None` for thumbs.
+    pub fn hand(self) -> Option<Hand> {
+        match self {
+            Finger::LeftPinky | Finger::LeftRing | Finger::LeftMiddle | Finger::LeftIndex => {
+                Some(Hand::Left)
+            }
+            Finger::RightIndex | Finger::RightMiddle | Finger::RightRing | Finger::RightPinky => {
+                Some(Hand::Right)
+            }
+            Finger::Thumb => None,
+        }
+    }
+}
+
+impl Hand {
+    /// An array of both hands.
+    pub const ALL_HANDS: [Hand; 2] = [Hand::Left, Hand::Right];
+}
+
+impl Row {
+    /// An array of all the rows, from top to bottom.
+    pub const ALL_ROWS: [Row; 5] = [Row::Number, Row::Top, Row::Home, Row::Bottom, Row::Thumb];
+
+    /// An array of the rows that are defined by a layout.
+    pub const LAYOUT_ROWS: [Row; 4] = [Row::Number, Row::Top, Row::Home, Row::Bottom];
+
+    /// Gets the finger that types the key in a particular column of this row.
+    pub fn finger(self, column: usize) -> Finger {
+        let fingers = match self {
+            Row::Number => &NUMBER_ROW_FINGERS,
+            Row::Top | Row::Home | Row::Bottom => &LETTER_ROW_FINGERS,
+            Row::Thumb => return Finger::Thumb,
+        };
+
+        fingers[column.min(MAX_ROW_KEYS - 1)]
+    }
+
+    /// Gets the name of this row in a layout file.
+    fn field_name(self) -> &'static str {
+        match self {
+            Row::Number => "number",
+            Row::Top => "top",
+            Row::Home => "home",
+            Row::Bottom => "bottom",
+            Row::Thumb => "thumb",
+        }
+    }
+
+    /// Gets the index of this row in a layout's rows, if it is defined by a layout.
+    fn layout_index(self) -> Option<usize> {
+        Row::LAYOUT_ROWS.iter().position(|row| *row == self)
+    }
+}
+
+impl KeyboardLayout {
+    /// Parses a keyboard layout from the contents of a layout file.
+    ///
+    /// Layout files consist of `field = value` lines, and lines starting with `#` are ignored.
+    /// The `name` field is the name of the layout, while the `number`, `top`, `home` and `bottom`
+    /// fields list the keys in each row from left to right, separated by whitespace. Each key is
+    /// written as its unshifted character, optionally followed by its shifted character.
+    pub fn parse(text: &str) -> Result<Self, LayoutError> {
+        let mut name = None;
+        let mut rows: [Option<Vec<LayoutKey>>; 4] = Default::default();
+        let mut keys = HashMap::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (field, value) = match line.find('=') {
+                Some(pos) => (line[..pos].trim(), line[pos + 1..].trim()),
+                None => {
+                    return Err(LayoutError::UnknownField {
+                        line: line_number,
+                        field: line.to_string(),
+                    })
+                }
+            };
+
+            if field == "name" {
+                name = Some(value.to_string());
+                continue;
+            }
+
+            let row = Row::LAYOUT_ROWS
+                .iter()
+                .copied()
+                .find(|row| row.field_name() == field)
+                .ok_or_else(|| LayoutError::UnknownField {
+                    line: line_number,
+                    field: field.to_string(),
+                })?;
+
+            let row_keys = parse_row(value, line_number)?;
+            if row_keys.len() > MAX_ROW_KEYS {
+                return Err(LayoutError::TooManyKeys { line: line_number });
+            }
+
+            for (column, key) in row_keys.iter().enumerate() {
+                let position = KeyPosition { row, column };
+                let finger = row.finger(column);
+                let chars =
+                    std::iter::once((key.unshifted, false)).chain(key.shifted.map(|c| (c, true)));
+
+                for (c, shifted) in chars {
+                    let info = KeyInfo {
+                        position,
+                        finger,
+                        shifted,
+                    };
+
+                    if keys.insert(c, info).is_some() {
+                        return Err(LayoutError::DuplicateChar {
+                            line: line_number,
+                            c,
+                        });
+                    }
+                }
+            }
+
+            rows[row.layout_index().unwrap()] = Some(row_keys);
+        }
+
+        let name = name.ok_or(LayoutError::MissingName)?;
+        let [number, top, home, bottom] = rows;
+        let rows = [
+            number.ok_or(LayoutError::MissingRow(Row::Number))?,
+            top.ok_or(LayoutError::MissingRow(Row::Top))?,
+            home.ok_or(LayoutError::MissingRow(Row::Home))?,
+            bottom.ok_or(LayoutError::MissingRow(Row::Bottom))?,
+        ];
+
+        Ok(Self { name, rows, keys })
+    }
+
+    /// Loads a keyboard layout from a layout file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LayoutError> {
+        let text = fs::read_to_string(path).map_err(LayoutError::Io)?;
+        Self::parse(&text)
+    }
+
+    /// Gets the name of this layout.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the keys in a row, from left to right.
+    pub fn keys(&self, row: Row) -> &[LayoutKey] {
+        match row.layout_index() {
+            Some(index) => &self.rows[index],
+            None => &[],
+        }
+    }
+
+    /// Finds where a character is typed in this layout, including spaces.
+    pub fn key_info(&self, c: char) -> Option<KeyInfo> {
+        if c == ' ' {
+            return Some(KeyInfo {
+                position: KeyPosition {
+                    row: Row::Thumb,
+                    column: 0,
+                },
+                finger: Finger::Thumb,
+                shifted: false,
+            });
+        }
+
+        self.keys.get(&c).copied()
+    }
+
+    /// Finds the finger that types a character, including the fixed Tab and Enter keys.
+    pub fn finger(&self, c: char) -> Option<Finger> {
+        match c {
+            '\t' => Some(Finger::LeftPinky),
+            '\n' => Some(Finger::RightPinky),
+            _ => self.key_info(c).map(|info| info.finger),
+        }
+    }
+
+    /// Gets the character produced by the key at a physical position.
+    pub fn char_at(&self, position: KeyPosition, shifted: bool) -> Option<char> {
+        if position.row == Row::Thumb {
+            return if position.column == 0 {
+                Some(' ')
+            } else {
+                None
+            };
+        }
+
+        let key = self.keys(position.row).get(position.column)?;
+        if shifted {
+            key.shifted
+        } else {
+            Some(key.unshifted)
+        }
+    }
+}
+
+impl LayoutEmulation {
+    /// Creates a new emulation of a target layout when typing on a physical layout.
+    pub fn new(physical: &KeyboardLayout, target: &KeyboardLayout) -> Self {
+        let mapping = physical
+            .keys
+            .iter()
+            .filter_map(|(c, info)| {
+                target
+                    .char_at(info.position, info.shifted)
+                    .map(|target_c| (*c, target_c))
+            })
+            .collect();
+
+        Self {
+            name: target.name.clone(),
+            mapping,
+        }
+    }
+
+    /// Gets the name of the layout that is being emulated.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Converts a character typed on the physical layout into the emulated layout.
+    ///
+    /// Characters that aren't in both layouts are left unchanged.
+    pub fn remap(&self, c: char) -> char {
+        self.mapping.get(&c).copied().unwrap_or(c)
+    }
+}
+
+impl Default for KeyboardLayout {
+    fn default() -> Self {
+        BuiltinLayout::Qwerty.layout()
+    }
+}
+
+impl BuiltinLayout {
+    /// An array of all the built-in layouts.
+    pub const ALL_LAYOUTS: [BuiltinLayout; 7] = [
+        BuiltinLayout::Qwerty,
+        BuiltinLayout::Azerty,
+        BuiltinLayout::Qwertz,
+        BuiltinLayout::Dvorak,
+        BuiltinLayout::Colemak,
+        BuiltinLayout::ColemakDh,
+        BuiltinLayout::Workman,
+    ];
+
+    /// Gets the contents of the layout file for this layout.
+    fn data(self) -> &'static str {
+        match self {
+            BuiltinLayout::Qwerty => include_str!("layouts/qwerty.layout"),
+            BuiltinLayout::Azerty => include_str!("layouts/azerty.layout"),
+            BuiltinLayout::Qwertz => include_str!("layouts/qwertz.layout"),
+            BuiltinLayout::Dvorak => include_str!("layouts/dvorak.layout"),
+            BuiltinLayout::Colemak => include_str!("layouts/colemak.layout"),
+            BuiltinLayout::ColemakDh => include_str!("layouts/colemak_dh.layout"),
+            BuiltinLayout::Workman => include_str!("layouts/workman.layout"),
+        }
+    }
+
+    /// Builds the keyboard layout for this built-in layout.
+    pub fn layout(self) -> KeyboardLayout {
+        KeyboardLayout::parse(self.data()).expect("Built-in layout is invalid!")
+    }
+}
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Hand::Left => f.write_str("Left Hand"),
+            Hand::Right => f.write_str("Right Hand"),
+        }
+    }
+}
+
+impl Display for Finger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Finger::LeftPinky => f.write_str("Left Pinky"),
+            Finger::LeftRing => f.write_str("Left Ring"),
+            Finger::LeftMiddle => f.write_str("Left Middle"),
+            Finger::LeftIndex => f.write_str("Left Index"),
+            Finger::Thumb => f.write_str("Thumbs"),
+            Finger::RightIndex => f.write_str("Right Index"),
+            Finger::RightMiddle => f.write_str("Right Middle"),
+            Finger::RightRing => f.write_str("Right Ring"),
+            Finger::RightPinky => f.write_str("Right Pinky"),
+        }
+    }
+}
+
+impl Display for Row {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Row::Number => f.write_str("Number Row"),
+            Row::Top => f.write_str("Top Row"),
+            Row::Home => f.write_str("Home Row"),
+            Row::Bottom => f.write_str("Bottom Row"),
+            Row::Thumb => f.write_str("Thumb Row"),
+        }
+    }
+}
+
+impl Display for BuiltinLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            BuiltinLayout::Qwerty => f.write_str("QWERTY"),
+            BuiltinLayout::Azerty => f.write_str("AZERTY"),
+            BuiltinLayout::Qwertz => f.write_str("QWERTZ"),
+            BuiltinLayout::Dvorak => f.write_str("Dvorak"),
+            BuiltinLayout::Colemak => f.write_str("Colemak"),
+            BuiltinLayout::ColemakDh => f.write_str("Colemak-DH"),
+            BuiltinLayout::Workman => f.write_str("Workman"),
+        }
+    }
+}
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LayoutError::Io(e) => write!(f, "{}", e),
+            LayoutError::MissingName => f.write_str("Layout has no name"),
+            LayoutError::MissingRow(row) => write!(f, "Layout has no {}", row),
+            LayoutError::UnknownField { line, field } => {
+                write!(f, "Line {}: unknown field '{}'", line, field)
+            }
+            LayoutError::InvalidKey { line, key } => {
+                write!(f, "Line {}: invalid key '{}'", line, key)
+            }
+            LayoutError::DuplicateChar { line, c } => {
+                write!(f, "Line {}: '{}' is already in the layout", line, c)
+            }
+            LayoutError::TooManyKeys { line } => {
+                write!(
+                    f,
+                    "Line {}: rows can have at most {} keys",
+                    line, MAX_ROW_KEYS
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// Parses the keys in a row of a layout file.
+fn parse_row(value: &str, line: usize) -> Result<Vec<LayoutKey>, LayoutError> {
+    value
+        .split_whitespace()
+        .map(|token| {
+            let mut chars = token.chars();
+            match (chars.next(), chars.next(), chars.next()) {
+                (Some(unshifted), shifted, None) => Ok(LayoutKey { unshifted, shifted }),
+                _ => Err(LayoutError::InvalidKey {
+                    line,
+                    key: token.to_string(),
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_layouts_are_valid() {
+        for layout in BuiltinLayout::ALL_LAYOUTS.iter() {
+            let parsed = KeyboardLayout::parse(layout.data());
+            assert!(parsed.is_ok(), "{}: {}", layout, parsed.unwrap_err());
+        }
+    }
+
+    #[test]
+    fn key_info_finds_positions_and_fingers() {
+        let layout = BuiltinLayout::Colemak.layout();
+
+        let info = layout.key_info('T').unwrap();
+        assert_eq!(
+            KeyPosition {
+                row: Row::Home,
+                column: 3
+            },
+            info.position
+        );
+        assert_eq!(Finger::LeftIndex, info.finger);
+        assert!(info.shifted);
+
+        assert_eq!(Some(Finger::Thumb), layout.finger(' '));
+        assert_eq!(Some(Finger::RightPinky), layout.finger('\n'));
+        assert_eq!(None, layout.key_info('é'));
+    }
+
+    #[test]
+    fn char_at_maps_between_layouts() {
+        let qwerty = BuiltinLayout::Qwerty.layout();
+        let colemak = BuiltinLayout::Colemak.layout();
+
+        let position = qwerty.key_info('k').unwrap().position;
+        assert_eq!(Some('e'), colemak.char_at(position, false));
+        assert_eq!(Some('E'), colemak.char_at(position, true));
+    }
+
+    #[test]
+    fn layout_emulation_remaps_by_position() {
+        let emulation = LayoutEmulation::new(
+            &BuiltinLayout::Qwerty.layout(),
+            &BuiltinLayout::Colemak.layout(),
+        );
+
+        assert_eq!("Colemak", emulation.name());
+        assert_eq!('e', emulation.remap('k'));
+        assert_eq!('O', emulation.remap(':'));
+        assert_eq!(' ', emulation.remap(' '));
+        assert_eq!('é', emulation.remap('é'));
+    }
+
+    #[test]
+    fn parse_reports_errors() {
+        assert!(matches!(
+            KeyboardLayout::parse("number = 1!"),
+            Err(LayoutError::MissingName)
+        ));
+        assert!(matches!(
+            KeyboardLayout::parse("name = Test\nnumber = 1! abc"),
+            Err(LayoutError::InvalidKey { line: 2, .. })
+        ));
+        assert!(matches!(
+            KeyboardLayout::parse("name = Test\nnumber = aA\ntop = a"),
+            Err(LayoutError::DuplicateChar { line: 3, c: 'a' })
+        ));
+        assert!(matches!(
+            KeyboardLayout::parse("name = Test\nnumber = 1\ntop = q\nhome = a"),
+            Err(LayoutError::MissingRow(Row::Bottom))
+        ));
+    }
+}
@@ -0,0 +1,502 @@
+use std::{collections::HashMap, fmt::Display, fs, io, path::Path};
+
+use crate::{
+    history::TestResult,
+    layouts::{Finger, KeyboardLayout, Row},
+};
+
+/// The WPM needed to pass a lesson, if the lesson doesn't set one.
+pub const DEFAULT_TARGET_WPM: u64 = 20;
+
+/// The accuracy needed to pass a lesson, if the lesson doesn't set one.
+pub const DEFAULT_TARGET_ACCURACY: f32 = 95.0;
+
+/// Represents a lesson, which introduces new letters on top of the letters from the lessons
+/// before it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lesson {
+    name: String,
+    new_letters: Vec<char>,
+    letters: Vec<char>,
+    target_wpm: u64,
+    target_accuracy: f32,
+}
+
+/// Represents an ordered set of lessons, where each lesson is unlocked by passing the lesson before
+/// it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Curriculum {
+    name: String,
+    lessons: Vec<Lesson>,
+}
+
+/// Identifies a lesson within a curriculum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LessonId {
+    pub curriculum: String,
+    pub index: usize,
+}
+
+/// Tracks the number of lessons that have been passed in each curriculum.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LessonProgress {
+    passed: HashMap<String, usize>,
+}
+
+/// Represents an error encountered while loading a curriculum.
+#[derive(Debug)]
+pub enum LessonError {
+    Io(io::Error),
+    MissingName,
+    NoLessons,
+    UnknownField { line: usize, field: String },
+    FieldOutsideLesson { line: usize, field: String },
+    InvalidValue { line: usize, value: String },
+    MissingLetters { lesson: String },
+}
+
+impl Lesson {
+    /// Gets the name of this lesson.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the letters introduced by this lesson.
+    pub fn new_letters(&self) -> &[char] {
+        &self.new_letters
+    }
+
+    /// Gets every letter that is typed in this lesson, including those from previous lessons.
+    pub fn letters(&self) -> &[char] {
+        &self.letters
+    }
+
+    /// Gets the WPM needed to pass this lesson.
+    pub fn target_wpm(&self) -> u64 {
+        self.target_wpm
+    }
+
+    /// Gets the accuracy needed to pass this lesson, as a percentage.
+    pub fn target_accuracy(&self) -> f32 {
+        self.target_accuracy
+    }
+
+    /// Checks whether a test result meets the targets for this lesson.
+    ///
+    /// Failed tests never pass a lesson.
+    pub fn is_passed(&self, result: &TestResult) -> bool {
+        result.stats.get_fail_reason().is_none()
+            && result.wpm().map_or(false, |wpm| wpm >= self.target_wpm)
+            && result
+                .accuracy()
+                .map_or(false, |accuracy| accuracy >= self.target_accuracy)
+    }
+}
+
+impl Curriculum {
+    /// Builds the default curriculum for a keyboard layout, which starts on the home row and then
+    /// adds the top and bottom rows.
+    ///
+    /// Each row is introduced in two lessons: the keys typed with the index fingers, then the
+    /// rest of the row. Only letters are included.
+    pub fn for_layout(layout: &KeyboardLayout) -> Self {
+        let mut lessons = LessonBuilder::default();
+
+        for &(row, row_name) in [
+            (Row::Home, "Home Row"),
+            (Row::Top, "Top Row"),
+            (Row::Bottom, "Bottom Row"),
+        ]
+        .iter()
+        {
+            let (index_letters, other_letters): (Vec<_>, Vec<_>) = layout
+                .keys(row)
+                .iter()
+                .enumerate()
+                .filter(|(_, key)| key.unshifted.is_alphabetic())
+                .partition(|(column, _)| {
+                    matches!(row.finger(*column), Finger::LeftIndex | Finger::RightIndex)
+                });
+
+            lessons.push(
+                format!("{}: Index Fingers", row_name),
+                index_letters.into_iter().map(|(_, key)| key.unshifted),
+            );
+            lessons.push(
+                row_name.to_string(),
+                other_letters.into_iter().map(|(_, key)| key.unshifted),
+            );
+        }
+
+        Self {
+            name: format!("Touch Typing ({})", layout.name()),
+            lessons: lessons.build(),
+        }
+    }
+
+    /// Parses a curriculum from the contents of a lessons file.
+    ///
+    /// Lessons files consist of `field = value` lines, and lines starting with `#` are ignored.
+    /// The `name` field is the name of the curriculum, and each `lesson` field starts a new lesson
+    /// with the provided name. Each lesson needs a `letters` field, listing the letters it
+    /// introduces. The `target_wpm` and `target_accuracy` fields are optional.
+    pub fn parse(text: &str) -> Result<Self, LessonError> {
+        let mut name = None;
+        let mut lessons: Vec<Lesson> = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (field, value) = match line.find('=') {
+                Some(pos) => (line[..pos].trim(), line[pos + 1..].trim()),
+                None => {
+                    return Err(LessonError::UnknownField {
+                        line: line_number,
+                        field: line.to_string(),
+                    })
+                }
+            };
+
+            match field {
+                "name" => {
+                    name = Some(value.to_string());
+                    continue;
+                }
+                "lesson" => {
+                    lessons.push(Lesson {
+                        name: value.to_string(),
+                        new_letters: Vec::new(),
+                        letters: Vec::new(),
+                        target_wpm: DEFAULT_TARGET_WPM,
+                        target_accuracy: DEFAULT_TARGET_ACCURACY,
+                    });
+                    continue;
+                }
+                "letters" | "target_wpm" | "target_accuracy" => {}
+                _ => {
+                    return Err(LessonError::UnknownField {
+                        line: line_number,
+                        field: field.to_string(),
+                    })
+                }
+            }
+
+            let lesson = lessons
+                .last_mut()
+                .ok_or_else(|| LessonError::FieldOutsideLesson {
+                    line: line_number,
+                    field: field.to_string(),
+                })?;
+
+            let invalid_value = || LessonError::InvalidValue {
+                line: line_number,
+                value: value.to_string(),
+            };
+
+            match field {
+                "letters" => {
+                    lesson.new_letters = value.chars().filter(|c| !c.is_whitespace()).collect()
+                }
+                "target_wpm" => lesson.target_wpm = value.parse().map_err(|_| invalid_value())?,
+                _ => {
+                    lesson.target_accuracy = value
+                        .parse()
+                        .ok()
+                        .filter(|accuracy| (0.0..=100.0).contains(accuracy))
+                        .ok_or_else(invalid_value)?
+                }
+            }
+        }
+
+        let name = name.ok_or(LessonError::MissingName)?;
+        if lessons.is_empty() {
+            return Err(LessonError::NoLessons);
+        }
+
+        // Each lesson also uses the letters from the lessons before it
+        let mut letters = Vec::new();
+        for lesson in lessons.iter_mut() {
+            if lesson.new_letters.is_empty() {
+                return Err(LessonError::MissingLetters {
+                    lesson: lesson.name.clone(),
+                });
+            }
+
+            add_letters(&mut letters, lesson.new_letters.iter().copied());
+            lesson.letters = letters.clone();
+        }
+
+        Ok(Self { name, lessons })
+    }
+
+    /// Loads a curriculum from a lessons file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LessonError> {
+        let text = fs::read_to_string(path).map_err(LessonError::Io)?;
+        Self::parse(&text)
+    }
+
+    /// Gets the name of this curriculum.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the lessons in this curriculum, in the order they are unlocked.
+    pub fn lessons(&self) -> &[Lesson] {
+        &self.lessons
+    }
+
+    /// Gets a lesson in this curriculum, if it exists.
+    pub fn lesson(&self, id: &LessonId) -> Option<&Lesson> {
+        if id.curriculum == self.name {
+            self.lessons.get(id.index)
+        } else {
+            None
+        }
+    }
+}
+
+impl LessonProgress {
+    /// Creates a new progress tracker, where no lessons have been passed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the progress from the contents of a progress file.
+    ///
+    /// Each line contains the number of lessons passed in a curriculum, followed by the name of
+    /// the curriculum. Invalid lines are skipped.
+    pub fn parse(text: &str) -> Self {
+        let passed = text
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                let pos = line.find(' ')?;
+                let count = line[..pos].parse().ok()?;
+                Some((line[pos + 1..].to_string(), count))
+            })
+            .collect();
+
+        Self { passed }
+    }
+
+    /// Converts the progress to the contents of a progress file, sorted by curriculum name.
+    pub fn encode(&self) -> String {
+        let mut passed: Vec<_> = self.passed.iter().collect();
+        passed.sort();
+
+        passed
+            .into_iter()
+            .map(|(curriculum, count)| format!("{} {}\n", count, curriculum))
+            .collect()
+    }
+
+    /// Gets the number of lessons that have been passed in a curriculum.
+    pub fn passed(&self, curriculum: &str) -> usize {
+        self.passed.get(curriculum).copied().unwrap_or(0)
+    }
+
+    /// Checks whether a lesson can be started, i.e. every lesson before it has been passed.
+    pub fn is_unlocked(&self, id: &LessonId) -> bool {
+        id.index <= self.passed(&id.curriculum)
+    }
+
+    /// Records that a lesson was passed.
+    ///
+    /// Returns `true` if this unlocked the next lesson, i.e. the lesson hadn't been passed before.
+    pub fn pass(&mut self, id: &LessonId) -> bool {
+        let passed = self.passed.entry(id.curriculum.clone()).or_insert(0);
+        if id.index == *passed {
+            *passed += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Display for LessonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LessonError::Io(e) => write!(f, "{}", e),
+            LessonError::MissingName => f.write_str("Curriculum has no name"),
+            LessonError::NoLessons => f.write_str("Curriculum has no lessons"),
+            LessonError::UnknownField { line, field } => {
+                write!(f, "Line {}: unknown field '{}'", line, field)
+            }
+            LessonError::FieldOutsideLesson { line, field } => {
+                write!(f, "Line {}: '{}' must come after a lesson", line, field)
+            }
+            LessonError::InvalidValue { line, value } => {
+                write!(f, "Line {}: invalid value '{}'", line, value)
+            }
+            LessonError::MissingLetters { lesson } => {
+                write!(f, "Lesson '{}' has no letters", lesson)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LessonError {}
+
+/// Helper for building lessons that each add letters to the lessons before them.
+#[derive(Default)]
+struct LessonBuilder {
+    lessons: Vec<Lesson>,
+    letters: Vec<char>,
+}
+
+impl LessonBuilder {
+    /// Adds a lesson which introduces the provided letters, skipping it if they are all in
+    /// previous lessons.
+    fn push(&mut self, name: String, new_letters: impl IntoIterator<Item = char>) {
+        let mut new_letters: Vec<_> = new_letters
+            .into_iter()
+            .filter(|c| !self.letters.contains(c))
+            .collect();
+        new_letters.dedup();
+
+        if new_letters.is_empty() {
+            return;
+        }
+
+        add_letters(&mut self.letters, new_letters.iter().copied());
+        self.lessons.push(Lesson {
+            name,
+            new_letters,
+            letters: self.letters.clone(),
+            target_wpm: DEFAULT_TARGET_WPM,
+            target_accuracy: DEFAULT_TARGET_ACCURACY,
+        });
+    }
+
+    fn build(self) -> Vec<Lesson> {
+        self.lessons
+    }
+}
+
+/// Adds letters to a set of letters, skipping any that are already in it.
+fn add_letters(letters: &mut Vec<char>, new_letters: impl IntoIterator<Item = char>) {
+    for c in new_letters {
+        if !letters.contains(&c) {
+            letters.push(c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::{
+        layouts::BuiltinLayout,
+        modes::ConfidenceMode,
+        stats::{TestCheckpoint, TestStats},
+    };
+
+    use super::*;
+
+    #[test]
+    fn parse_adds_letters_from_previous_lessons() {
+        let text = "# Comment\n\
+                    name = Basics\n\
+                    lesson = Index Fingers\n\
+                    letters = f j\n\
+                    lesson = Home Row\n\
+                    letters = asdfjkl\n\
+                    target_wpm = 30\n\
+                    target_accuracy = 97.5\n";
+        let curriculum = Curriculum::parse(text).unwrap();
+        let lessons = curriculum.lessons();
+
+        assert_eq!("Basics", curriculum.name());
+        assert_eq!(2, lessons.len());
+        assert_eq!(&['f', 'j'], lessons[0].letters());
+        assert_eq!(DEFAULT_TARGET_WPM, lessons[0].target_wpm());
+        assert_eq!("fjasdkl", lessons[1].letters().iter().collect::<String>());
+        assert_eq!(30, lessons[1].target_wpm());
+        assert_eq!(97.5, lessons[1].target_accuracy());
+    }
+
+    #[rstest(
+        text,
+        expected,
+        case("lesson = A\nletters = a", "Curriculum has no name"),
+        case("name = X", "Curriculum has no lessons"),
+        case("name = X\nletters = a", "Line 2: 'letters' must come after a lesson"),
+        case("name = X\nlesson = A\nkeys = a", "Line 3: unknown field 'keys'"),
+        case(
+            "name = X\nlesson = A\nletters = a\ntarget_accuracy = 120",
+            "Line 4: invalid value '120'"
+        ),
+        case("name = X\nlesson = A", "Lesson 'A' has no letters")
+    )]
+    fn parse_reports_errors(text: &str, expected: &str) {
+        assert_eq!(expected, Curriculum::parse(text).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn for_layout_starts_with_home_row_index_fingers() {
+        let curriculum = Curriculum::for_layout(&BuiltinLayout::Qwerty.layout());
+        let lessons = curriculum.lessons();
+
+        assert_eq!("Touch Typing (QWERTY)", curriculum.name());
+        assert_eq!(6, lessons.len());
+        assert_eq!(&['f', 'g', 'h', 'j'], lessons[0].letters());
+        assert_eq!(&['a', 's', 'd', 'k', 'l'], lessons[1].new_letters());
+        assert_eq!(26, lessons[5].letters().len());
+    }
+
+    #[rstest(
+        correct_chars,
+        incorrect_chars,
+        expected,
+        case(150, 0, true),
+        case(100, 5, true),
+        case(150, 17, false),
+        case(50, 0, false)
+    )]
+    fn is_passed_checks_targets(correct_chars: u64, incorrect_chars: u64, expected: bool) {
+        let curriculum = Curriculum::parse("name = X\nlesson = A\nletters = a").unwrap();
+        let lesson = &curriculum.lessons()[0];
+
+        // The default targets are 20 WPM, i.e. 100 characters per minute, with 95% accuracy
+        let stats = TestStats::from_checkpoint(TestCheckpoint {
+            elapsed: Duration::from_secs(60),
+            correct_chars,
+            incorrect_chars,
+            correct_words: 0,
+            incorrect_words: 0,
+            blocked_attempts: 0,
+        });
+
+        let result = TestResult::new(stats, ConfidenceMode::Off);
+        assert_eq!(expected, lesson.is_passed(&result));
+    }
+
+    #[test]
+    fn progress_unlocks_lessons_in_order() {
+        let id = |index| LessonId {
+            curriculum: String::from("Basics"),
+            index,
+        };
+        let mut progress = LessonProgress::new();
+
+        assert!(progress.is_unlocked(&id(0)));
+        assert!(!progress.is_unlocked(&id(1)));
+
+        assert!(progress.pass(&id(0)));
+        assert!(!progress.pass(&id(0)));
+        assert!(progress.is_unlocked(&id(1)));
+        assert!(!progress.is_unlocked(&id(2)));
+
+        assert_eq!(progress, LessonProgress::parse(&progress.encode()));
+        assert_eq!("1 Basics\n", progress.encode());
+    }
+}
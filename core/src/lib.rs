@@ -1,3 +1,9 @@
+pub mod history;
+pub mod layouts;
+pub mod lessons;
+pub mod line_layout;
+pub mod modes;
+pub mod replay;
 pub mod stats;
 
 pub mod word_generators;
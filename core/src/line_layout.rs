@@ -0,0 +1,330 @@
+//! Lays out the words from a word generator into lines that fit the available width.
+
+use std::collections::VecDeque;
+
+use crate::word_generators::{DisplayedWord, Separator, WordGenerator};
+
+/// The number of characters requested from a word generator each time more words are needed.
+///
+/// This only affects how many words are generated at once, not where lines break.
+const GENERATOR_CHUNK_CHARS: usize = 80;
+
+/// Measures how wide words are when they are displayed.
+pub trait TextMeasure {
+    /// Gets the width of a word, including the marker shown after words submitted with a newline.
+    fn word_width(&self, word: &DisplayedWord) -> f32;
+
+    /// Gets the width of the gap between two words.
+    fn gap_width(&self) -> f32;
+}
+
+/// Measures text in a font where every character has the same width.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MonospaceMeasure {
+    /// The width of each character in a word.
+    pub char_width: f32,
+
+    /// The width of the gap between two words.
+    pub gap_width: f32,
+}
+
+/// Lays out words from a word generator into lines that fit within a maximum width.
+///
+/// Words that don't fit on a line are kept for the next line, so changing the width only changes
+/// where lines break, not which words are shown.
+#[derive(Default)]
+pub struct LineLayout {
+    pending: VecDeque<DisplayedWord>,
+}
+
+impl MonospaceMeasure {
+    /// Measures words in columns, where each character and gap is one column wide.
+    pub const COLUMNS: MonospaceMeasure = MonospaceMeasure {
+        char_width: 1.0,
+        gap_width: 1.0,
+    };
+}
+
+impl TextMeasure for MonospaceMeasure {
+    fn word_width(&self, word: &DisplayedWord) -> f32 {
+        word.marked_len() as f32 * self.char_width
+    }
+
+    fn gap_width(&self) -> f32 {
+        self.gap_width
+    }
+}
+
+impl LineLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fills a line with the next words, generating more words when needed.
+    ///
+    /// The line is left empty if the word generator has run out of words.
+    pub fn fill_line(
+        &mut self,
+        generator: &mut dyn WordGenerator,
+        measure: &impl TextMeasure,
+        max_width: f32,
+        line: &mut Vec<DisplayedWord>,
+    ) {
+        line.clear();
+        self.extend_line(generator, measure, max_width, line);
+    }
+
+    /// Continues filling a line with the next words, keeping the words already on it.
+    ///
+    /// Nothing is added if the line already ends with a word submitted with a newline.
+    pub fn extend_line(
+        &mut self,
+        generator: &mut dyn WordGenerator,
+        measure: &impl TextMeasure,
+        max_width: f32,
+        line: &mut Vec<DisplayedWord>,
+    ) {
+        if line
+            .last()
+            .map_or(false, |w| w.separator == Separator::Newline)
+        {
+            return;
+        }
+
+        let mut width = line_width(line, measure);
+        loop {
+            if self.pending.is_empty() {
+                let mut chunk = Vec::new();
+                generator.fill_line(&mut chunk, GENERATOR_CHUNK_CHARS);
+                if chunk.is_empty() {
+                    return;
+                }
+
+                self.pending.extend(chunk);
+            }
+
+            let word_width = measure.word_width(&self.pending[0]);
+            let new_width = if line.is_empty() {
+                word_width
+            } else {
+                width + measure.gap_width() + word_width
+            };
+
+            // The first word is always added, so that a word wider than the line isn't stuck
+            if !line.is_empty() && new_width > max_width {
+                return;
+            }
+
+            let word = self.pending.pop_front().unwrap();
+            let separator = word.separator;
+
+            width = new_width;
+            line.push(word);
+
+            if separator == Separator::Newline {
+                return;
+            }
+        }
+    }
+
+    /// Returns words that were laid out but not typed, so that they are laid out again in the same
+    /// order before any new words.
+    pub fn unplace(&mut self, words: impl DoubleEndedIterator<Item = DisplayedWord>) {
+        for word in words.rev() {
+            self.pending.push_front(word);
+        }
+    }
+
+    /// Discards any words that haven't been laid out yet.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+/// Splits words into lines that fit within a maximum width, where words submitted with a newline
+/// always end a line.
+pub fn split_lines<'a>(
+    words: &'a [DisplayedWord],
+    measure: &impl TextMeasure,
+    max_width: f32,
+) -> Vec<&'a [DisplayedWord]> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut width = 0.0;
+
+    for (i, word) in words.iter().enumerate() {
+        let word_width = measure.word_width(word);
+        if i > start && width + measure.gap_width() + word_width > max_width {
+            lines.push(&words[start..i]);
+            start = i;
+        }
+
+        width = if i == start {
+            word_width
+        } else {
+            width + measure.gap_width() + word_width
+        };
+
+        if word.separator == Separator::Newline {
+            lines.push(&words[start..=i]);
+            start = i + 1;
+        }
+    }
+
+    if start < words.len() {
+        lines.push(&words[start..]);
+    }
+
+    lines
+}
+
+/// Gets the width of a line of words, including the gaps between them.
+fn line_width(line: &[DisplayedWord], measure: &impl TextMeasure) -> f32 {
+    let words: f32 = line.iter().map(|w| measure.word_width(w)).sum();
+    let gaps = line.len().saturating_sub(1) as f32 * measure.gap_width();
+    words + gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use crate::word_generators::custom::{CustomTextOptions, CustomWordGenerator};
+
+    use super::*;
+
+    const TEXT: &str = "the quick brown fox jumps over the lazy dog";
+
+    fn generator() -> CustomWordGenerator {
+        let options = CustomTextOptions {
+            repeat: false,
+            ..CustomTextOptions::default()
+        };
+        CustomWordGenerator::new(TEXT, options)
+    }
+
+    fn words(line: &[DisplayedWord]) -> Vec<&str> {
+        line.iter().map(|w| w.word.as_str()).collect()
+    }
+
+    fn all_lines(
+        layout: &mut LineLayout,
+        generator: &mut CustomWordGenerator,
+        max_width: f32,
+    ) -> Vec<Vec<String>> {
+        let mut lines = Vec::new();
+        let mut line = Vec::new();
+
+        loop {
+            layout.fill_line(generator, &MonospaceMeasure::COLUMNS, max_width, &mut line);
+            if line.is_empty() {
+                return lines;
+            }
+
+            lines.push(line.iter().map(|w| w.word.clone()).collect());
+        }
+    }
+
+    #[rstest(
+        max_width,
+        expected,
+        case(15.0, vec!["the quick brown", "fox jumps over", "the lazy dog"]),
+        case(20.0, vec!["the quick brown fox", "jumps over the lazy", "dog"]),
+        case(3.0, vec!["the", "quick", "brown", "fox", "jumps", "over", "the", "lazy", "dog"])
+    )]
+    fn lines_fit_max_width(max_width: f32, expected: Vec<&str>) {
+        let mut layout = LineLayout::new();
+        let lines: Vec<String> = all_lines(&mut layout, &mut generator(), max_width)
+            .into_iter()
+            .map(|line| line.join(" "))
+            .collect();
+
+        assert_eq!(expected, lines);
+    }
+
+    #[test]
+    fn measure_scales_widths() {
+        let measure = MonospaceMeasure {
+            char_width: 10.0,
+            gap_width: 5.0,
+        };
+        let mut layout = LineLayout::new();
+        let mut line = Vec::new();
+
+        // "the quick" is 80 wide, plus 5 for the gap
+        layout.fill_line(&mut generator(), &measure, 85.0, &mut line);
+        assert_eq!(vec!["the", "quick"], words(&line));
+    }
+
+    #[test]
+    fn unplaced_words_are_laid_out_again_in_order() {
+        let mut generator = generator();
+        let mut layout = LineLayout::new();
+        let mut current_line = Vec::new();
+        let mut next_line = Vec::new();
+
+        layout.fill_line(
+            &mut generator,
+            &MonospaceMeasure::COLUMNS,
+            15.0,
+            &mut current_line,
+        );
+        layout.fill_line(
+            &mut generator,
+            &MonospaceMeasure::COLUMNS,
+            15.0,
+            &mut next_line,
+        );
+
+        // Keep the first word, then reflow the rest with a wider line
+        let rest = current_line.split_off(1);
+        layout.unplace(rest.into_iter().chain(next_line.drain(..)));
+        layout.extend_line(
+            &mut generator,
+            &MonospaceMeasure::COLUMNS,
+            30.0,
+            &mut current_line,
+        );
+        layout.fill_line(
+            &mut generator,
+            &MonospaceMeasure::COLUMNS,
+            30.0,
+            &mut next_line,
+        );
+
+        assert_eq!(
+            vec!["the", "quick", "brown", "fox", "jumps", "over"],
+            words(&current_line)
+        );
+        assert_eq!(vec!["the", "lazy", "dog"], words(&next_line));
+    }
+
+    #[test]
+    fn newlines_end_lines() {
+        let mut generator =
+            CustomWordGenerator::new("one two\nthree", CustomTextOptions::default());
+        let mut layout = LineLayout::new();
+        let mut line = Vec::new();
+
+        layout.fill_line(&mut generator, &MonospaceMeasure::COLUMNS, 80.0, &mut line);
+        assert_eq!(vec!["one", "two"], words(&line));
+
+        layout.extend_line(&mut generator, &MonospaceMeasure::COLUMNS, 80.0, &mut line);
+        assert_eq!(vec!["one", "two"], words(&line));
+    }
+
+    #[test]
+    fn split_lines_matches_layout() {
+        let mut layout = LineLayout::new();
+        let expected = all_lines(&mut layout, &mut generator(), 15.0);
+
+        let mut all_words = Vec::new();
+        generator().fill_line(&mut all_words, 80);
+        let lines: Vec<Vec<String>> = split_lines(&all_words, &MonospaceMeasure::COLUMNS, 15.0)
+            .into_iter()
+            .map(|line| line.iter().map(|w| w.word.clone()).collect())
+            .collect();
+
+        assert_eq!(expected, lines);
+    }
+}
@@ -0,0 +1,278 @@
+use std::fmt::Display;
+
+use crate::stats::TestCheckpoint;
+
+/// Represents how incorrect input is handled during a typing test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopOnError {
+    /// Incorrect letters and words are accepted.
+    Off,
+
+    /// Incorrect letters are blocked until the correct letter is typed.
+    Letter,
+
+    /// Words cannot be submitted until they match.
+    Word,
+}
+
+impl StopOnError {
+    /// An array of all the stop on error modes.
+    pub const ALL_MODES: [StopOnError; 3] =
+        [StopOnError::Off, StopOnError::Letter, StopOnError::Word];
+}
+
+impl Default for StopOnError {
+    fn default() -> Self {
+        StopOnError::Off
+    }
+}
+
+impl Display for StopOnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            StopOnError::Off => f.write_str("Off"),
+            StopOnError::Letter => f.write_str("Stop on Letter"),
+            StopOnError::Word => f.write_str("Stop on Word"),
+        }
+    }
+}
+
+/// Represents which mistakes can be corrected during a typing test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConfidenceMode {
+    /// Mistakes can be corrected.
+    Off,
+
+    /// Mistakes can only be corrected within the current word, so Backspace and Delete do nothing
+    /// once its input is empty.
+    On,
+
+    /// Backspace and Delete are disabled, and characters can only be added to the end of the
+    /// input, so mistakes cannot be corrected.
+    Max,
+}
+
+impl ConfidenceMode {
+    /// An array of all the confidence modes.
+    pub const ALL_MODES: [ConfidenceMode; 3] =
+        [ConfidenceMode::Off, ConfidenceMode::On, ConfidenceMode::Max];
+
+    /// Checks whether Backspace and Delete can be used with the current input.
+    pub fn allows_deletion(&self, input: &str) -> bool {
+        match *self {
+            ConfidenceMode::Off => true,
+            ConfidenceMode::On => !input.is_empty(),
+            ConfidenceMode::Max => false,
+        }
+    }
+
+    /// Checks whether the input can be changed from `previous` to `new`.
+    ///
+    /// At max confidence, characters can only be added to the end of the input. This also covers
+    /// corrections which don't use Backspace or Delete, such as typing over a selection or cutting it.
+    pub fn allows_edit(&self, previous: &str, new: &str) -> bool {
+        match *self {
+            ConfidenceMode::Off | ConfidenceMode::On => true,
+            ConfidenceMode::Max => new.starts_with(previous),
+        }
+    }
+}
+
+impl Default for ConfidenceMode {
+    fn default() -> Self {
+        ConfidenceMode::Off
+    }
+}
+
+impl Display for ConfidenceMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ConfidenceMode::Off => f.write_str("Off"),
+            ConfidenceMode::On => f.write_str("On (Current Word Only)"),
+            ConfidenceMode::Max => f.write_str("Max (No Backspace)"),
+        }
+    }
+}
+
+/// Represents the optional conditions that end a typing test early with a failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FailConditions {
+    /// Whether the test fails as soon as an incorrect word is submitted.
+    pub sudden_death: bool,
+
+    /// The minimum running accuracy, as a percentage.
+    pub min_accuracy: Option<u64>,
+
+    /// The minimum live WPM, which is only enforced after the grace period.
+    pub min_wpm: Option<u64>,
+
+    /// The number of seconds before the minimum WPM is enforced.
+    pub wpm_grace_seconds: u64,
+}
+
+/// Represents the reason that a typing test failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FailReason {
+    IncorrectWord,
+    AccuracyTooLow(u64),
+    WpmTooLow(u64),
+}
+
+impl FailConditions {
+    /// Checks whether a submitted word fails the test.
+    pub fn check_word(&self, is_correct: bool) -> Option<FailReason> {
+        if self.sudden_death && !is_correct {
+            Some(FailReason::IncorrectWord)
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether the latest statistics checkpoint fails the test.
+    pub fn check_checkpoint(&self, checkpoint: &TestCheckpoint) -> Option<FailReason> {
+        if let Some(min_accuracy) = self.min_accuracy {
+            if checkpoint
+                .accuracy()
+                .map_or(false, |a| a < min_accuracy as f32)
+            {
+                return Some(FailReason::AccuracyTooLow(min_accuracy));
+            }
+        }
+
+        if let Some(min_wpm) = self.min_wpm {
+            if checkpoint.elapsed.as_secs() >= self.wpm_grace_seconds
+                && checkpoint
+                    .effective_wpm()
+                    .map_or(false, |wpm| wpm < min_wpm)
+            {
+                return Some(FailReason::WpmTooLow(min_wpm));
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for FailConditions {
+    fn default() -> Self {
+        Self {
+            sudden_death: false,
+            min_accuracy: None,
+            min_wpm: None,
+            wpm_grace_seconds: 5,
+        }
+    }
+}
+
+impl Display for FailReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            FailReason::IncorrectWord => f.write_str("An incorrect word was submitted"),
+            FailReason::AccuracyTooLow(accuracy) => {
+                write!(f, "Accuracy dropped below {}%", accuracy)
+            }
+            FailReason::WpmTooLow(wpm) => write!(f, "WPM dropped below {}", wpm),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    use rstest::*;
+
+    fn checkpoint(elapsed_secs: u64, correct_chars: u64, incorrect_chars: u64) -> TestCheckpoint {
+        TestCheckpoint {
+            elapsed: Duration::from_secs(elapsed_secs),
+
+            correct_chars,
+            incorrect_chars,
+            correct_words: 0,
+            incorrect_words: 0,
+            blocked_attempts: 0,
+        }
+    }
+
+    #[test]
+    fn check_word_only_fails_with_sudden_death() {
+        let mut conditions = FailConditions::default();
+        assert_eq!(None, conditions.check_word(false));
+
+        conditions.sudden_death = true;
+        assert_eq!(None, conditions.check_word(true));
+        assert_eq!(
+            Some(FailReason::IncorrectWord),
+            conditions.check_word(false)
+        );
+    }
+
+    #[rstest(
+        min_accuracy,
+        min_wpm,
+        checkpoint,
+        expected,
+        case(
+            Some(90),
+            None,
+            checkpoint(1, 80, 20),
+            Some(FailReason::AccuracyTooLow(90))
+        ),
+        case(Some(90), None, checkpoint(1, 95, 5), None),
+        case(Some(90), None, checkpoint(1, 0, 0), None),
+        case(None, Some(40), checkpoint(2, 10, 0), None),
+        case(
+            None,
+            Some(40),
+            checkpoint(60, 100, 0),
+            Some(FailReason::WpmTooLow(40))
+        ),
+        case(None, Some(40), checkpoint(60, 250, 0), None)
+    )]
+    fn check_checkpoint(
+        min_accuracy: Option<u64>,
+        min_wpm: Option<u64>,
+        checkpoint: TestCheckpoint,
+        expected: Option<FailReason>,
+    ) {
+        let conditions = FailConditions {
+            min_accuracy,
+            min_wpm,
+            ..FailConditions::default()
+        };
+
+        assert_eq!(expected, conditions.check_checkpoint(&checkpoint));
+    }
+    #[rstest(
+        mode,
+        previous,
+        new,
+        expected,
+        case(ConfidenceMode::Off, "cay", "ca", true),
+        case(ConfidenceMode::On, "cay", "ca", true),
+        case(ConfidenceMode::Max, "ca", "cat", true),
+        case(ConfidenceMode::Max, "cay", "ca", false),
+        case(ConfidenceMode::Max, "cay", "cat", false),
+        case(ConfidenceMode::Max, "cay", "", false)
+    )]
+    fn allows_edit(mode: ConfidenceMode, previous: &str, new: &str, expected: bool) {
+        assert_eq!(expected, mode.allows_edit(previous, new));
+    }
+
+    #[rstest(
+        mode,
+        input,
+        expected,
+        case(ConfidenceMode::Off, "", true),
+        case(ConfidenceMode::On, "", false),
+        case(ConfidenceMode::On, "ca", true),
+        case(ConfidenceMode::Max, "ca", false)
+    )]
+    fn allows_deletion(mode: ConfidenceMode, input: &str, expected: bool) {
+        assert_eq!(expected, mode.allows_deletion(input));
+    }
+}
@@ -0,0 +1,482 @@
+use std::{fmt::Display, fs, io, path::Path, time::Duration};
+
+use crate::{
+    line_layout::{self, TextMeasure},
+    word_generators::{DisplayedWord, Separator, WordStatus},
+};
+
+/// The first line of every replay file, which includes the version of the format.
+const REPLAY_HEADER: &str = "typetest-replay 1";
+
+/// Stores every change to the input during a typing test, so that the test can be played back.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Replay {
+    words: Vec<ReplayWord>,
+    events: Vec<ReplayEvent>,
+}
+
+/// Represents a word that was reached during a typing test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReplayWord {
+    pub word: String,
+    pub separator: Separator,
+}
+
+/// Represents a single change to the input during a typing test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReplayEvent {
+    /// The time since the start of the test.
+    pub elapsed: Duration,
+    pub kind: ReplayEventKind,
+}
+
+/// Represents the kinds of change to the input during a typing test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReplayEventKind {
+    /// Characters were removed from the end of the current word, then other characters were added.
+    Edit { delete: usize, insert: String },
+
+    /// The current word was submitted using a separator.
+    Submit(Separator),
+}
+
+/// Represents the state of a typing test at a point during a replay.
+pub struct ReplayFrame {
+    /// The words that were reached during the test, with their status at this point.
+    pub words: Vec<DisplayedWord>,
+
+    /// The index of the word being typed, which is past the last word if every word has been
+    /// submitted.
+    pub current: usize,
+
+    /// The input for the word being typed.
+    pub input: String,
+}
+
+/// Represents an error that occurred while loading a replay.
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    InvalidHeader,
+    InvalidLine { line: usize, contents: String },
+}
+
+impl Replay {
+    /// Creates a new, empty replay.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the words that were reached during the test (in the order they were typed).
+    pub fn words(&self) -> &[ReplayWord] {
+        &self.words
+    }
+
+    /// Gets the changes to the input during the test (in the order they happened).
+    pub fn events(&self) -> &[ReplayEvent] {
+        &self.events
+    }
+
+    /// Gets the time of the last change to the input.
+    pub fn duration(&self) -> Duration {
+        self.events
+            .last()
+            .map(|event| event.elapsed)
+            .unwrap_or_default()
+    }
+
+    /// Removes all the words and events, for the next test.
+    pub(crate) fn clear(&mut self) {
+        self.words.clear();
+        self.events.clear();
+    }
+
+    /// Records a change to the input for the word at `index`.
+    pub(crate) fn record_input(
+        &mut self,
+        elapsed: Duration,
+        index: usize,
+        expected: &str,
+        previous_input: &str,
+        input: &str,
+    ) {
+        self.reach_word(index, expected, Separator::Space);
+
+        let common = previous_input
+            .chars()
+            .zip(input.chars())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let delete = previous_input.chars().count() - common;
+        let insert: String = input.chars().skip(common).collect();
+        if delete == 0 && insert.is_empty() {
+            return;
+        }
+
+        self.push(elapsed, ReplayEventKind::Edit { delete, insert });
+    }
+
+    /// Records that the word at `index` was submitted.
+    pub(crate) fn record_submit(
+        &mut self,
+        elapsed: Duration,
+        index: usize,
+        expected: &str,
+        expected_separator: Separator,
+        actual_separator: Separator,
+    ) {
+        self.reach_word(index, expected, expected_separator);
+        self.words[index].separator = expected_separator;
+        self.push(elapsed, ReplayEventKind::Submit(actual_separator));
+    }
+
+    /// Adds the word at `index` if it hasn't been reached before.
+    fn reach_word(&mut self, index: usize, expected: &str, separator: Separator) {
+        if index == self.words.len() {
+            self.words.push(ReplayWord {
+                word: expected.to_string(),
+                separator,
+            });
+        }
+    }
+
+    fn push(&mut self, elapsed: Duration, kind: ReplayEventKind) {
+        self.events.push(ReplayEvent { elapsed, kind });
+    }
+
+    /// Reconstructs the state of the test after all the changes up to `elapsed`.
+    pub fn frame_at(&self, elapsed: Duration) -> ReplayFrame {
+        // The extra input is for when every word that was reached has been submitted
+        let mut inputs = vec![String::new(); self.words.len() + 1];
+        let mut separators = vec![None; inputs.len()];
+        let mut current = 0;
+
+        for event in self.events.iter().take_while(|e| e.elapsed <= elapsed) {
+            match &event.kind {
+                ReplayEventKind::Edit { delete, insert } => {
+                    let input = &mut inputs[current];
+                    let keep = input.chars().count().saturating_sub(*delete);
+                    *input = input.chars().take(keep).chain(insert.chars()).collect();
+                }
+                ReplayEventKind::Submit(separator) => {
+                    separators[current] = Some(*separator);
+                    current = (current + 1).min(self.words.len());
+                }
+            }
+        }
+
+        let words = self
+            .words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                let input = &inputs[i];
+                let status = match separators[i] {
+                    Some(separator) if *input == word.word && separator == word.separator => {
+                        WordStatus::Correct
+                    }
+                    Some(_) => WordStatus::Incorrect,
+                    None if word.word.starts_with(input.as_str()) => WordStatus::NotTyped,
+                    None => WordStatus::Incorrect,
+                };
+
+                let mut displayed =
+                    DisplayedWord::with_separator(word.word.clone(), word.separator);
+                displayed.status = status;
+                displayed
+            })
+            .collect();
+
+        ReplayFrame {
+            words,
+            current,
+            input: inputs.swap_remove(current),
+        }
+    }
+
+    /// Converts this replay into the text of a replay file.
+    ///
+    /// Each word is written on a line starting with `w`, followed by each event on a line starting
+    /// with the number of milliseconds since the previous event.
+    pub fn encode(&self) -> String {
+        let mut text = String::from(REPLAY_HEADER);
+        text.push('\n');
+
+        for word in &self.words {
+            text.push_str(&format!(
+                "w {} {}\n",
+                encode_separator(word.separator),
+                escape(&word.word)
+            ));
+        }
+
+        let mut previous = Duration::default();
+        for event in &self.events {
+            let delta = event.elapsed.checked_sub(previous).unwrap_or_default();
+            previous = event.elapsed;
+
+            let line = match &event.kind {
+                ReplayEventKind::Edit { delete, insert } => {
+                    format!("{} e {} {}", delta.as_millis(), delete, escape(insert))
+                }
+                ReplayEventKind::Submit(separator) => {
+                    format!("{} s {}", delta.as_millis(), encode_separator(*separator))
+                }
+            };
+
+            text.push_str(&line);
+            text.push('\n');
+        }
+
+        text
+    }
+
+    /// Parses the text of a replay file.
+    pub fn parse(text: &str) -> Result<Self, ReplayError> {
+        let mut lines = text.lines();
+        if lines.next().map(str::trim_end) != Some(REPLAY_HEADER) {
+            return Err(ReplayError::InvalidHeader);
+        }
+
+        let mut replay = Replay::new();
+        let mut elapsed = Duration::default();
+
+        for (i, line) in lines.enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let invalid = || ReplayError::InvalidLine {
+                line: i + 2,
+                contents: line.to_string(),
+            };
+
+            let mut fields = line.splitn(3, ' ');
+            let first = fields.next().ok_or_else(invalid)?;
+
+            if first == "w" {
+                let separator = fields
+                    .next()
+                    .and_then(decode_separator)
+                    .ok_or_else(invalid)?;
+                let word = fields.next().and_then(unescape).ok_or_else(invalid)?;
+                replay.words.push(ReplayWord { word, separator });
+                continue;
+            }
+
+            let delta: u64 = first.parse().map_err(|_| invalid())?;
+            elapsed += Duration::from_millis(delta);
+
+            let kind = match fields.next() {
+                Some("e") => {
+                    let mut edit = fields.next().unwrap_or_default().splitn(2, ' ');
+                    let delete = edit.next().and_then(|d| d.parse().ok());
+                    let insert = edit.next().and_then(unescape);
+                    match (delete, insert) {
+                        (Some(delete), Some(insert)) => ReplayEventKind::Edit { delete, insert },
+                        _ => return Err(invalid()),
+                    }
+                }
+                Some("s") => ReplayEventKind::Submit(
+                    fields
+                        .next()
+                        .and_then(decode_separator)
+                        .ok_or_else(invalid)?,
+                ),
+                _ => return Err(invalid()),
+            };
+
+            replay.push(elapsed, kind);
+        }
+
+        Ok(replay)
+    }
+
+    /// Loads a replay file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let text = fs::read_to_string(path).map_err(ReplayError::Io)?;
+        Self::parse(&text)
+    }
+
+    /// Saves this replay to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.encode())
+    }
+}
+
+impl ReplayFrame {
+    /// Splits the words into lines that fit within a maximum width, where words submitted with a
+    /// newline always end a line.
+    pub fn lines(&self, measure: &impl TextMeasure, max_width: f32) -> Vec<&[DisplayedWord]> {
+        line_layout::split_lines(&self.words, measure, max_width)
+    }
+}
+
+fn encode_separator(separator: Separator) -> char {
+    match separator {
+        Separator::Space => 's',
+        Separator::Newline => 'n',
+    }
+}
+
+fn decode_separator(value: &str) -> Option<Separator> {
+    match value {
+        "s" => Some(Separator::Space),
+        "n" => Some(Separator::Newline),
+        _ => None,
+    }
+}
+
+/// Escapes backslashes and line breaks, so that text fits on a single line.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Reverses [`escape`], or returns `None` if the text has an unknown escape sequence.
+fn unescape(text: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            unescaped.push(match chars.next()? {
+                '\\' => '\\',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            });
+        } else {
+            unescaped.push(c);
+        }
+    }
+
+    Some(unescaped)
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Io(e) => write!(f, "{}", e),
+            ReplayError::InvalidHeader => f.write_str("File is not a TypeTest replay"),
+            ReplayError::InvalidLine { line, contents } => {
+                write!(f, "Line {}: invalid entry '{}'", line, contents)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::line_layout::MonospaceMeasure;
+
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    /// Types "cat" as "cay", corrects it to "cat", then submits "dig" for "dog".
+    fn sample_replay() -> Replay {
+        let mut replay = Replay::new();
+        replay.record_input(ms(100), 0, "cat", "", "c");
+        replay.record_input(ms(200), 0, "cat", "c", "ca");
+        replay.record_input(ms(300), 0, "cat", "ca", "cay");
+        replay.record_input(ms(400), 0, "cat", "cay", "ca");
+        replay.record_input(ms(500), 0, "cat", "ca", "cat");
+        replay.record_submit(ms(600), 0, "cat", Separator::Space, Separator::Space);
+        replay.record_input(ms(700), 1, "dog", "", "dig");
+        replay.record_submit(ms(800), 1, "dog", Separator::Newline, Separator::Newline);
+        replay
+    }
+
+    #[test]
+    fn record_input_stores_edits() {
+        let replay = sample_replay();
+
+        assert_eq!(2, replay.words().len());
+        assert_eq!(Separator::Newline, replay.words()[1].separator);
+        assert_eq!(
+            ReplayEventKind::Edit {
+                delete: 1,
+                insert: String::new(),
+            },
+            replay.events()[3].kind
+        );
+        assert_eq!(ms(800), replay.duration());
+    }
+
+    #[test]
+    fn frame_at_shows_corrections() {
+        let replay = sample_replay();
+
+        let frame = replay.frame_at(ms(350));
+        assert_eq!(0, frame.current);
+        assert_eq!("cay", frame.input);
+        assert_eq!(WordStatus::Incorrect, frame.words[0].status);
+
+        let frame = replay.frame_at(ms(850));
+        assert_eq!(2, frame.current);
+        assert_eq!("", frame.input);
+        assert_eq!(WordStatus::Correct, frame.words[0].status);
+        assert_eq!(WordStatus::Incorrect, frame.words[1].status);
+    }
+
+    #[test]
+    fn encode_and_parse_round_trip() {
+        let mut replay = sample_replay();
+        replay.record_input(ms(1000), 1, "dog", "dig", "\tdo\\g\n");
+
+        let parsed = Replay::parse(&replay.encode()).unwrap();
+        assert_eq!(replay, parsed);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_files() {
+        assert!(matches!(
+            Replay::parse("not a replay"),
+            Err(ReplayError::InvalidHeader)
+        ));
+        assert!(matches!(
+            Replay::parse("typetest-replay 1\nw s cat\n10 x\n"),
+            Err(ReplayError::InvalidLine { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn lines_wrap_words() {
+        let mut replay = Replay::new();
+        for (i, word) in ["aaaa", "bbbb", "cccc", "dd"].iter().enumerate() {
+            let separator = if i == 2 {
+                Separator::Newline
+            } else {
+                Separator::Space
+            };
+            replay.record_submit(ms(0), i, word, separator, separator);
+        }
+
+        let frame = replay.frame_at(ms(0));
+        let lengths: Vec<_> = frame
+            .lines(&MonospaceMeasure::COLUMNS, 10.0)
+            .iter()
+            .map(|line| line.len())
+            .collect();
+        assert_eq!(vec![2, 1, 1], lengths);
+    }
+}
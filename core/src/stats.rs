@@ -1,25 +1,50 @@
 use std::{
-    cmp::Ordering,
+    collections::HashMap,
     time::{Duration, Instant},
 };
 
+use crate::{
+    layouts::KeyboardLayout, modes::FailReason, replay::Replay, word_generators::Separator,
+};
+
+use self::{
+    fingers::FingerStats,
+    keys::{KeyStats, Keystroke},
+    missed::MissedWordGroup,
+    ngrams::NgramStats,
+};
+
+pub mod fingers;
+pub mod keys;
+pub mod missed;
+pub mod ngrams;
+pub mod scoring;
+
 /// Stores statistics for a typing test.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TestStats {
+    #[cfg_attr(feature = "serde", serde(skip, default = "Instant::now"))]
     test_start: Instant,
     checkpoints: Vec<TestCheckpoint>,
     missed_words: Vec<MissedWord>,
+    word_scores: Vec<WordScore>,
+    keystrokes: Vec<Keystroke>,
+    replay: Replay,
+    fail_reason: Option<FailReason>,
 
     correct_chars: u64,
     incorrect_chars: u64,
     correct_words: u64,
     incorrect_words: u64,
+    blocked_attempts: u64,
 }
 
 /// Represents a statistics checkpoint in a typing test.
 ///
 /// Eventually, this will be used for graphing WPM.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TestCheckpoint {
     pub elapsed: Duration,
 
@@ -27,10 +52,32 @@ pub struct TestCheckpoint {
     pub incorrect_chars: u64,
     pub correct_words: u64,
     pub incorrect_words: u64,
+
+    /// The number of incorrect letters or words that were blocked by a stop on error mode.
+    pub blocked_attempts: u64,
+}
+
+/// Represents the typing speed over a period of a typing test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WpmWindow {
+    pub start: Duration,
+    pub end: Duration,
+    pub raw_wpm: u64,
+}
+
+/// Represents how a submitted word contributed to the statistics, so that it can be reverted.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct WordScore {
+    correct_chars: u64,
+    incorrect_chars: u64,
+    is_correct: bool,
 }
 
 /// Represents a missed word in a typing test.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MissedWord {
     pub expected: String,
     pub actual: String,
@@ -43,73 +90,304 @@ impl TestStats {
             test_start: Instant::now(),
             checkpoints: Vec::new(),
             missed_words: Vec::new(),
+            word_scores: Vec::new(),
+            keystrokes: Vec::new(),
+            replay: Replay::new(),
+            fail_reason: None,
 
             correct_chars: 0,
             incorrect_chars: 0,
             correct_words: 0,
             incorrect_words: 0,
+            blocked_attempts: 0,
+        }
+    }
+
+    /// Creates statistics for a test where only the final totals are known, such as a test that
+    /// was imported from another application.
+    pub fn from_checkpoint(checkpoint: TestCheckpoint) -> Self {
+        Self {
+            checkpoints: vec![checkpoint],
+
+            correct_chars: checkpoint.correct_chars,
+            incorrect_chars: checkpoint.incorrect_chars,
+            correct_words: checkpoint.correct_words,
+            incorrect_words: checkpoint.incorrect_words,
+            blocked_attempts: checkpoint.blocked_attempts,
+            ..Self::new()
         }
     }
 
+    /// Gets the replay of every change to the input during the test.
+    pub fn get_replay(&self) -> &Replay {
+        &self.replay
+    }
+
     /// Gets the list of missed words (in the order they were typed).
     pub fn get_missed_words(&self) -> &[MissedWord] {
         &self.missed_words
     }
 
+    /// Groups the missed words by the expected word, from the most to the least frequently missed.
+    pub fn missed_word_groups(&self) -> Vec<MissedWordGroup> {
+        missed::group_missed_words(&self.missed_words)
+    }
+
+    /// Gets the characters typed during the test (in the order they were typed).
+    pub fn get_keystrokes(&self) -> &[Keystroke] {
+        &self.keystrokes
+    }
+
+    /// Calculates the statistics for each key that needed to be typed during the test.
+    pub fn key_stats(&self) -> HashMap<char, KeyStats> {
+        keys::key_stats(&self.keystrokes)
+    }
+
+    /// Calculates the statistics for each finger, hand and row on the layout the test was typed on.
+    pub fn finger_stats(&self, layout: &KeyboardLayout) -> FingerStats {
+        fingers::finger_stats(&self.keystrokes, layout)
+    }
+
+    /// Calculates the statistics for each sequence of `n` characters typed within a word.
+    pub fn ngram_stats(&self, n: usize) -> HashMap<String, NgramStats> {
+        ngrams::ngram_stats(&self.keystrokes, n)
+    }
+
+    /// Gets the reason that the test failed, if it did.
+    pub fn get_fail_reason(&self) -> Option<FailReason> {
+        self.fail_reason
+    }
+
     /// Gets the latest statistics checkpoint.
     pub fn get_latest_checkpoint(&self) -> Option<&TestCheckpoint> {
         self.checkpoints.last()
     }
 
+    /// Calculates how consistent the typing speed was, as a percentage between 0 and 100.
+    ///
+    /// This uses the coefficient of variation of the raw WPM between each checkpoint, so steady
+    /// typing scores close to 100%. Returns `None` if there are too few checkpoints to compare.
+    pub fn consistency(&self) -> Option<f32> {
+        let wpms: Vec<f32> = self
+            .checkpoint_pairs()
+            .filter_map(|(previous, current)| {
+                let elapsed = current.elapsed.checked_sub(previous.elapsed)?;
+                if elapsed.as_secs_f32() > 0.0 {
+                    Some(
+                        total_chars(current).saturating_sub(total_chars(previous)) as f32
+                            / 5.0
+                            / elapsed.as_secs_f32()
+                            * 60.0,
+                    )
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        if wpms.len() < 2 {
+            return None;
+        }
+
+        let mean = wpms.iter().sum::<f32>() / wpms.len() as f32;
+        if mean <= 0.0 {
+            return None;
+        }
+
+        let variance = wpms.iter().map(|wpm| (wpm - mean).powi(2)).sum::<f32>() / wpms.len() as f32;
+        let coefficient_of_variation = variance.sqrt() / mean;
+
+        Some(((1.0 - coefficient_of_variation) * 100.0).max(0.0))
+    }
+
+    /// Finds the period of at least `length` with the highest raw WPM (i.e. the burst WPM).
+    pub fn fastest_window(&self, length: Duration) -> Option<WpmWindow> {
+        self.windows(length).max_by_key(|window| window.raw_wpm)
+    }
+
+    /// Finds the period of at least `length` with the lowest raw WPM.
+    pub fn slowest_window(&self, length: Duration) -> Option<WpmWindow> {
+        self.windows(length).min_by_key(|window| window.raw_wpm)
+    }
+
+    /// Iterates over each pair of consecutive checkpoints, starting from the beginning of the test.
+    fn checkpoint_pairs(&self) -> impl Iterator<Item = (&TestCheckpoint, &TestCheckpoint)> {
+        let starts = std::iter::once(&TestCheckpoint::START).chain(self.checkpoints.iter());
+        starts.zip(self.checkpoints.iter())
+    }
+
+    /// Iterates over the shortest periods of at least `length` that start at each checkpoint.
+    fn windows(&self, length: Duration) -> impl Iterator<Item = WpmWindow> + '_ {
+        let starts = std::iter::once(&TestCheckpoint::START).chain(self.checkpoints.iter());
+
+        starts.enumerate().filter_map(move |(i, start)| {
+            let end = self.checkpoints[i..]
+                .iter()
+                .find(|end| end.elapsed >= start.elapsed + length)?;
+
+            let chars = total_chars(end).saturating_sub(total_chars(start));
+
+            Some(WpmWindow {
+                start: start.elapsed,
+                end: end.elapsed,
+                raw_wpm: wpm(chars, end.elapsed - start.elapsed)?,
+            })
+        })
+    }
+
+    /// Takes the final checkpoint when the test ends, so that the results use the real elapsed time.
+    ///
+    /// If nothing has changed since the latest checkpoint, it's replaced rather than followed by an
+    /// identical checkpoint, so the final checkpoint doesn't skew the consistency or WPM windows.
+    pub fn finish(&mut self) {
+        let unchanged = self.checkpoints.last().map_or(false, |checkpoint| {
+            checkpoint.correct_chars == self.correct_chars
+                && checkpoint.incorrect_chars == self.incorrect_chars
+                && checkpoint.correct_words == self.correct_words
+                && checkpoint.incorrect_words == self.incorrect_words
+                && checkpoint.blocked_attempts == self.blocked_attempts
+        });
+
+        if unchanged {
+            self.checkpoints.pop();
+        }
+
+        self.checkpoint();
+    }
+
     /// Starts the next test.
     pub fn next_test(&mut self) {
         self.test_start = Instant::now();
         self.checkpoints.clear();
         self.missed_words.clear();
+        self.word_scores.clear();
+        self.keystrokes.clear();
+        self.replay.clear();
+        self.fail_reason = None;
 
         self.correct_chars = 0;
         self.incorrect_chars = 0;
         self.correct_words = 0;
         self.incorrect_words = 0;
+        self.blocked_attempts = 0;
     }
 
     /// Submits a word for the current test, returning whether it was correct or not.
     pub fn submit_word(&mut self, expected: &str, actual: &str) -> bool {
+        self.submit_word_with_separator(expected, actual, Separator::Space, Separator::Space)
+    }
+
+    /// Submits a word that was ended with a particular separator, returning whether it was correct or not.
+    ///
+    /// Comparison is whitespace-aware: leading indentation is compared separately from the rest of the word,
+    /// so that missing indentation does not misalign every other character, and the separator itself
+    /// is scored as a character.
+    pub fn submit_word_with_separator(
+        &mut self,
+        expected: &str,
+        actual: &str,
+        expected_separator: Separator,
+        actual_separator: Separator,
+    ) -> bool {
         if expected.is_empty() {
             return false;
         }
 
-        if expected == actual {
-            // NOTE: +1 to chars due to spacebar
-            self.correct_chars += expected.len() as u64 + 1;
+        let index = self.word_scores.len();
+        self.replay.record_submit(
+            self.elapsed(),
+            index,
+            expected,
+            expected_separator,
+            actual_separator,
+        );
+
+        let position = actual.chars().count();
+        self.record_keystroke(
+            position,
+            expected_char(expected, position, expected_separator),
+            actual_separator.as_char(),
+        );
+
+        let (prev_correct_chars, prev_incorrect_chars) = (self.correct_chars, self.incorrect_chars);
+        let is_correct = self.score_word(expected, actual, expected_separator, actual_separator);
+
+        self.word_scores.push(WordScore {
+            correct_chars: self.correct_chars - prev_correct_chars,
+            incorrect_chars: self.incorrect_chars - prev_incorrect_chars,
+            is_correct,
+        });
+
+        is_correct
+    }
+
+    /// Records the characters that were added to the end of the input for the current word.
+    ///
+    /// Characters that were removed or replaced aren't recorded as keystrokes, since they were
+    /// already recorded when they were first typed, but every change is recorded in the replay.
+    pub fn record_input(&mut self, expected: &str, previous_input: &str, input: &str) {
+        let index = self.word_scores.len();
+        self.replay
+            .record_input(self.elapsed(), index, expected, previous_input, input);
+
+        let common = previous_input
+            .chars()
+            .zip(input.chars())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        if common < previous_input.chars().count() {
+            return;
+        }
+
+        for (i, c) in input.chars().enumerate().skip(common) {
+            self.record_keystroke(i, expected.chars().nth(i), c);
+        }
+    }
+
+    /// Records a single typed character at a position in the current word.
+    fn record_keystroke(&mut self, position: usize, expected: Option<char>, actual: char) {
+        let elapsed = self.elapsed();
+        self.keystrokes
+            .push(Keystroke::new(elapsed, position, expected, actual));
+    }
+
+    /// Gets the time since the start of the test.
+    fn elapsed(&self) -> Duration {
+        Instant::now().duration_since(self.test_start)
+    }
+
+    /// Updates the statistics for a submitted word, returning whether it was correct or not.
+    fn score_word(
+        &mut self,
+        expected: &str,
+        actual: &str,
+        expected_separator: Separator,
+        actual_separator: Separator,
+    ) -> bool {
+        if expected == actual && expected_separator == actual_separator {
+            // NOTE: +1 to chars due to separator
+            self.correct_chars += expected.chars().count() as u64 + 1;
             self.correct_words += 1;
 
             true
         } else {
             self.incorrect_words += 1;
 
-            // Count how many characters are correct
-            expected
-                .chars()
-                .zip(actual.chars())
-                .for_each(|(e_char, a_char)| {
-                    if e_char == a_char {
-                        self.correct_chars += 1;
-                    } else {
-                        self.incorrect_chars += 1;
-                    }
-                });
-
-            // If word lengths match, then spacebar usage is correct
-            // Otherwise, spacebar usage is incorrect, and we have missing/extra chars
-            match expected.len().cmp(&actual.len()) {
-                Ordering::Equal => self.correct_chars += 1,
-                Ordering::Greater => {
-                    self.incorrect_chars += 1 + (expected.len() - actual.len()) as u64
-                }
-                Ordering::Less => {
-                    self.incorrect_chars += 1 + (actual.len() - expected.len()) as u64
-                }
+            // Count how many characters are correct, aligning on the indentation
+            let (expected_indent, expected_text) = split_indentation(expected);
+            let (actual_indent, actual_text) = split_indentation(actual);
+            self.compare_chars(expected_indent, actual_indent);
+            self.compare_chars(expected_text, actual_text);
+
+            // If word lengths match, then separator usage depends on which separator was typed
+            // Otherwise, separator usage is incorrect
+            if expected.chars().count() == actual.chars().count()
+                && expected_separator == actual_separator
+            {
+                self.correct_chars += 1;
+            } else {
+                self.incorrect_chars += 1;
             }
 
             self.missed_words.push(MissedWord::new(expected, actual));
@@ -118,6 +396,16 @@ impl TestStats {
         }
     }
 
+    /// Records an incorrect letter or word that was blocked from being entered.
+    pub fn block_attempt(&mut self) {
+        self.blocked_attempts += 1;
+    }
+
+    /// Marks the current test as failed.
+    pub fn fail(&mut self, reason: FailReason) {
+        self.fail_reason = Some(reason);
+    }
+
     /// Saves a statistics checkpoint the test.
     pub fn checkpoint(&mut self) {
         let checkpoint = TestCheckpoint {
@@ -127,29 +415,60 @@ impl TestStats {
             incorrect_chars: self.incorrect_chars,
             correct_words: self.correct_words,
             incorrect_words: self.incorrect_words,
+            blocked_attempts: self.blocked_attempts,
         };
 
         self.checkpoints.push(checkpoint);
     }
+
+    /// Compares the characters in two strings, counting any missing or extra characters as incorrect.
+    fn compare_chars(&mut self, expected: &str, actual: &str) {
+        let mut expected_chars = expected.chars();
+        let mut actual_chars = actual.chars();
+
+        loop {
+            match (expected_chars.next(), actual_chars.next()) {
+                (Some(e_char), Some(a_char)) if e_char == a_char => self.correct_chars += 1,
+                (None, None) => break,
+                _ => self.incorrect_chars += 1,
+            }
+        }
+    }
 }
 
 impl TestCheckpoint {
-    /// Calculates the accuracy for this checkpoint.
-    pub fn accuracy(&self) -> f32 {
-        (self.correct_chars as f32 / (self.correct_chars + self.incorrect_chars) as f32) * 100.0
+    /// A checkpoint representing the start of a test, before anything was typed.
+    const START: TestCheckpoint = TestCheckpoint {
+        elapsed: Duration::from_secs(0),
+
+        correct_chars: 0,
+        incorrect_chars: 0,
+        correct_words: 0,
+        incorrect_words: 0,
+        blocked_attempts: 0,
+    };
+
+    /// Calculates the accuracy for this checkpoint, or `None` if nothing has been typed.
+    /// Blocked attempts are not included, since they are counted separately from characters.
+    pub fn accuracy(&self) -> Option<f32> {
+        let total = total_chars(self);
+        if total == 0 {
+            None
+        } else {
+            Some(self.correct_chars as f32 / total as f32 * 100.0)
+        }
     }
 
-    /// Calculates the effective WPM for this checkpoint.
+    /// Calculates the effective WPM for this checkpoint, or `None` if no time has elapsed.
     /// Uses 1 WPM = 5 CPM for this calculation.
-    pub fn effective_wpm(&self) -> u64 {
-        (self.correct_chars as f32 / 5.0 / self.elapsed.as_secs_f32() * 60.0) as u64
+    pub fn effective_wpm(&self) -> Option<u64> {
+        wpm(self.correct_chars, self.elapsed)
     }
 
-    /// Calculates the raw WPM for this checkpoint.
+    /// Calculates the raw WPM for this checkpoint, or `None` if no time has elapsed.
     /// Uses 1 WPM = 5 CPM for this calculation.
-    pub fn raw_wpm(&self) -> u64 {
-        ((self.correct_chars + self.incorrect_chars) as f32 / 5.0 / self.elapsed.as_secs_f32()
-            * 60.0) as u64
+    pub fn raw_wpm(&self) -> Option<u64> {
+        wpm(total_chars(self), self.elapsed)
     }
 }
 
@@ -163,6 +482,42 @@ impl MissedWord {
     }
 }
 
+/// Calculates the WPM for a number of characters typed over a period, using 1 WPM = 5 CPM.
+///
+/// Returns `None` if the period is empty, since the WPM is undefined.
+fn wpm(chars: u64, elapsed: Duration) -> Option<u64> {
+    let seconds = elapsed.as_secs_f32();
+    if seconds > 0.0 {
+        Some((chars as f32 / 5.0 / seconds * 60.0) as u64)
+    } else {
+        None
+    }
+}
+
+/// Counts the characters that were typed by a checkpoint, whether they were correct or not.
+fn total_chars(checkpoint: &TestCheckpoint) -> u64 {
+    checkpoint.correct_chars + checkpoint.incorrect_chars
+}
+
+/// Gets the character that was expected after a number of characters have been typed, where the
+/// separator is expected once the whole word has been typed.
+fn expected_char(expected: &str, typed: usize, separator: Separator) -> Option<char> {
+    let len = expected.chars().count();
+    if typed < len {
+        expected.chars().nth(typed)
+    } else if typed == len {
+        Some(separator.as_char())
+    } else {
+        None
+    }
+}
+
+/// Splits a word into its leading indentation and the remaining text.
+fn split_indentation(word: &str) -> (&str, &str) {
+    let text = word.trim_start_matches('\t');
+    (&word[..word.len() - text.len()], text)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,11 +585,183 @@ mod tests {
                 .get_missed_words()
                 .contains(&MissedWord::new(expected, actual)));
         }
+
+        #[rstest(
+            expected,
+            actual,
+            expected_separator,
+            actual_separator,
+            correct_chars,
+            incorrect_chars,
+            case("\tlet", "let", Separator::Space, Separator::Space, 3, 2),
+            case("\tlet", "\t\tlet", Separator::Space, Separator::Space, 4, 2),
+            case("{", "{", Separator::Newline, Separator::Space, 1, 1),
+            case("{", "(", Separator::Newline, Separator::Newline, 1, 1)
+        )]
+        fn submit_word_with_separator_compares_whitespace(
+            expected: &str,
+            actual: &str,
+            expected_separator: Separator,
+            actual_separator: Separator,
+            correct_chars: u64,
+            incorrect_chars: u64,
+        ) {
+            let mut stats = TestStats::new();
+
+            let is_correct = stats.submit_word_with_separator(
+                expected,
+                actual,
+                expected_separator,
+                actual_separator,
+            );
+
+            assert!(!is_correct);
+
+            assert_eq!(correct_chars, stats.correct_chars);
+            assert_eq!(incorrect_chars, stats.incorrect_chars);
+        }
+
+        #[test]
+        fn submit_word_with_newline_separator_counts_newline() {
+            let mut stats = TestStats::new();
+            let is_correct = stats.submit_word_with_separator(
+                "\t}",
+                "\t}",
+                Separator::Newline,
+                Separator::Newline,
+            );
+
+            assert!(is_correct);
+
+            // 1 char for tab, 1 for brace, 1 for enter
+            assert_eq!(3, stats.correct_chars);
+            assert_eq!(0, stats.incorrect_chars);
+        }
+    }
+
+    mod test_keystrokes {
+        use super::*;
+
+        fn typed(stats: &TestStats) -> Vec<(Option<char>, char)> {
+            stats
+                .get_keystrokes()
+                .iter()
+                .map(|k| (k.expected, k.actual))
+                .collect()
+        }
+
+        #[test]
+        fn record_input_records_appended_characters() {
+            let mut stats = TestStats::new();
+            stats.record_input("cat", "", "c");
+            stats.record_input("cat", "c", "cx");
+            stats.record_input("cat", "cx", "c");
+            stats.record_input("cat", "c", "cat");
+
+            assert_eq!(
+                vec![
+                    (Some('c'), 'c'),
+                    (Some('a'), 'x'),
+                    (Some('a'), 'a'),
+                    (Some('t'), 't')
+                ],
+                typed(&stats)
+            );
+        }
+
+        #[test]
+        fn record_input_records_positions() {
+            let mut stats = TestStats::new();
+            stats.record_input("cat", "", "cx");
+            stats.record_input("cat", "cx", "c");
+            stats.record_input("cat", "c", "cat");
+            stats.submit_word("cat", "cat");
+
+            let positions: Vec<_> = stats.get_keystrokes().iter().map(|k| k.position).collect();
+            assert_eq!(vec![0, 1, 1, 2, 3], positions);
+        }
+
+        #[test]
+        fn submit_word_records_separator() {
+            let mut stats = TestStats::new();
+            stats.submit_word("cat", "cat");
+            stats.submit_word("cat", "ca");
+            stats.submit_word("cat", "cats");
+
+            assert_eq!(
+                vec![(Some(' '), ' '), (Some('t'), ' '), (None, ' ')],
+                typed(&stats)
+            );
+        }
     }
 
     mod test_checkpoint {
         use super::*;
 
+        /// Creates test statistics with a checkpoint each second, where `chars` is the number of
+        /// characters typed during each second.
+        fn stats_with_chars_per_second(chars: &[u64]) -> TestStats {
+            let mut stats = TestStats::new();
+            let mut total = 0;
+
+            for (i, c) in chars.iter().enumerate() {
+                total += c;
+                stats.checkpoints.push(TestCheckpoint {
+                    elapsed: Duration::from_secs(i as u64 + 1),
+                    correct_chars: total,
+                    ..TestCheckpoint::START
+                });
+            }
+
+            stats
+        }
+
+        #[test]
+        fn consistency_is_full_for_steady_typing() {
+            let stats = stats_with_chars_per_second(&[5, 5, 5, 5]);
+            assert!((100.0 - stats.consistency().unwrap()).abs() < 0.01);
+        }
+
+        #[test]
+        fn consistency_decreases_with_variation() {
+            // Per-second WPM is 60, 120, 60, 120: a mean of 90 with a standard deviation of 30
+            let stats = stats_with_chars_per_second(&[5, 10, 5, 10]);
+            assert!((66.67 - stats.consistency().unwrap()).abs() < 0.01);
+        }
+
+        #[test]
+        fn consistency_needs_multiple_checkpoints() {
+            assert_eq!(None, stats_with_chars_per_second(&[]).consistency());
+            assert_eq!(None, stats_with_chars_per_second(&[5]).consistency());
+            assert_eq!(None, stats_with_chars_per_second(&[0, 0]).consistency());
+        }
+
+        #[test]
+        fn fastest_and_slowest_windows() {
+            let stats = stats_with_chars_per_second(&[5, 10, 15, 0, 5]);
+            let length = Duration::from_secs(2);
+
+            assert_eq!(
+                Some(WpmWindow {
+                    start: Duration::from_secs(1),
+                    end: Duration::from_secs(3),
+                    raw_wpm: 150,
+                }),
+                stats.fastest_window(length)
+            );
+
+            assert_eq!(
+                Some(WpmWindow {
+                    start: Duration::from_secs(3),
+                    end: Duration::from_secs(5),
+                    raw_wpm: 30,
+                }),
+                stats.slowest_window(length)
+            );
+
+            assert_eq!(None, stats.fastest_window(Duration::from_secs(10)));
+        }
+
         #[rstest(
             correct_chars,
             incorrect_chars,
@@ -251,9 +778,66 @@ mod tests {
                 incorrect_chars,
                 correct_words: 0,
                 incorrect_words: 0,
+                blocked_attempts: 0,
             };
 
-            assert!((expected_accuracy - checkpoint.accuracy()) < 0.01);
+            assert!((expected_accuracy - checkpoint.accuracy().unwrap()).abs() < 0.01);
+        }
+
+        #[test]
+        fn accuracy_excludes_blocked_attempts() {
+            let checkpoint = TestCheckpoint {
+                elapsed: Duration::from_secs(0),
+
+                correct_chars: 15,
+                incorrect_chars: 0,
+                correct_words: 0,
+                incorrect_words: 0,
+                blocked_attempts: 5,
+            };
+
+            assert!((100.0 - checkpoint.accuracy().unwrap()).abs() < 0.01);
+        }
+
+        #[test]
+        fn empty_checkpoint_has_no_accuracy_or_wpm() {
+            assert_eq!(None, TestCheckpoint::START.accuracy());
+            assert_eq!(None, TestCheckpoint::START.effective_wpm());
+            assert_eq!(None, TestCheckpoint::START.raw_wpm());
+        }
+
+        #[test]
+        fn instant_checkpoint_has_no_wpm() {
+            let checkpoint = TestCheckpoint {
+                correct_chars: 10,
+                ..TestCheckpoint::START
+            };
+
+            assert_eq!(Some(100.0), checkpoint.accuracy());
+            assert_eq!(None, checkpoint.effective_wpm());
+            assert_eq!(None, checkpoint.raw_wpm());
+        }
+
+        #[test]
+        fn finish_always_takes_a_checkpoint() {
+            let mut stats = TestStats::new();
+            stats.finish();
+            assert_eq!(1, stats.checkpoints.len());
+
+            stats.submit_word("cat", "cat");
+            stats.finish();
+            assert_eq!(2, stats.checkpoints.len());
+            assert_eq!(4, stats.get_latest_checkpoint().unwrap().correct_chars);
+        }
+
+        #[test]
+        fn finish_replaces_unchanged_checkpoint() {
+            let mut stats = TestStats::new();
+            stats.submit_word("cat", "cat");
+            stats.checkpoint();
+            stats.finish();
+
+            assert_eq!(1, stats.checkpoints.len());
         }
 
         #[rstest(
@@ -277,9 +861,10 @@ mod tests {
                 incorrect_chars,
                 correct_words: 0,
                 incorrect_words: 0,
+                blocked_attempts: 0,
             };
 
-            assert_eq!(expected_wpm, checkpoint.effective_wpm());
+            assert_eq!(Some(expected_wpm), checkpoint.effective_wpm());
         }
 
         #[rstest(
@@ -298,9 +883,10 @@ mod tests {
                 incorrect_chars,
                 correct_words: 0,
                 incorrect_words: 0,
+                blocked_attempts: 0,
             };
 
-            assert_eq!(expected_wpm, checkpoint.raw_wpm());
+            assert_eq!(Some(expected_wpm), checkpoint.raw_wpm());
         }
     }
 }
@@ -0,0 +1,127 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::layouts::{Finger, Hand, KeyInfo, KeyboardLayout, Row};
+
+use super::keys::{key_stats, KeyStats, Keystroke};
+
+/// Represents the statistics for a typing test, grouped by the finger, hand and row of each key.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FingerStats {
+    pub fingers: HashMap<Finger, KeyStats>,
+    pub hands: HashMap<Hand, KeyStats>,
+    pub rows: HashMap<Row, KeyStats>,
+
+    /// The number of pairs of consecutive keys that were both on the layout.
+    pub bigrams: u64,
+
+    /// The number of bigrams that used the same finger for two different keys.
+    pub same_finger_bigrams: u64,
+}
+
+impl FingerStats {
+    /// Calculates the proportion of bigrams that used the same finger, between 0 and 1.
+    pub fn same_finger_bigram_rate(&self) -> f32 {
+        if self.bigrams == 0 {
+            0.0
+        } else {
+            self.same_finger_bigrams as f32 / self.bigrams as f32
+        }
+    }
+}
+
+/// Calculates the statistics for each finger, hand and row from a sequence of keystrokes.
+///
+/// Keys are assigned to fingers using standard touch typing on the provided layout. Characters
+/// that aren't on the layout are ignored.
+pub fn finger_stats(keystrokes: &[Keystroke], layout: &KeyboardLayout) -> FingerStats {
+    let mut stats = FingerStats::default();
+
+    for (c, char_stats) in key_stats(keystrokes) {
+        if let Some(finger) = layout.finger(c) {
+            merge_into(&mut stats.fingers, finger, &char_stats);
+
+            if let Some(hand) = finger.hand() {
+                merge_into(&mut stats.hands, hand, &char_stats);
+            }
+        }
+
+        if let Some(info) = layout.key_info(c) {
+            merge_into(&mut stats.rows, info.position.row, &char_stats);
+        }
+    }
+
+    let expected_keys = keystrokes
+        .iter()
+        .map(|keystroke| keystroke.expected.and_then(|c| layout.key_info(c)));
+
+    let mut previous: Option<KeyInfo> = None;
+    for key in expected_keys {
+        if let (Some(previous), Some(current)) = (previous, key) {
+            stats.bigrams += 1;
+
+            if previous.finger == current.finger
+                && previous.finger != Finger::Thumb
+                && previous.position != current.position
+            {
+                stats.same_finger_bigrams += 1;
+            }
+        }
+
+        previous = key;
+    }
+
+    stats
+}
+
+/// Adds the statistics for a key to a group of statistics.
+fn merge_into<K: Eq + Hash>(groups: &mut HashMap<K, KeyStats>, group: K, stats: &KeyStats) {
+    groups.entry(group).or_default().merge(stats);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::layouts::BuiltinLayout;
+
+    use super::*;
+
+    fn keystrokes(expected: &str, actual: &str) -> Vec<Keystroke> {
+        expected
+            .chars()
+            .zip(actual.chars())
+            .enumerate()
+            .map(|(i, (e, a))| Keystroke::new(Duration::from_millis(i as u64 * 100), i, Some(e), a))
+            .collect()
+    }
+
+    #[test]
+    fn finger_stats_groups_keys() {
+        let layout = BuiltinLayout::Qwerty.layout();
+        let stats = finger_stats(&keystrokes("sw j", "sx j"), &layout);
+
+        assert_eq!(2, stats.fingers[&Finger::LeftRing].presses);
+        assert_eq!(1, stats.fingers[&Finger::LeftRing].errors);
+        assert_eq!(1, stats.fingers[&Finger::RightIndex].presses);
+        assert_eq!(1, stats.fingers[&Finger::Thumb].presses);
+
+        assert_eq!(2, stats.hands[&Hand::Left].presses);
+        assert_eq!(1, stats.hands[&Hand::Right].presses);
+
+        assert_eq!(2, stats.rows[&Row::Home].presses);
+        assert_eq!(1, stats.rows[&Row::Top].presses);
+        assert_eq!(1, stats.rows[&Row::Thumb].presses);
+    }
+
+    #[test]
+    fn finger_stats_counts_same_finger_bigrams() {
+        let layout = BuiltinLayout::Qwerty.layout();
+
+        // "ed", "de" and "ec" are same finger bigrams, but "ee" repeats a key and "  " uses thumbs
+        let stats = finger_stats(&keystrokes("edeec  ", "edeec  "), &layout);
+
+        assert_eq!(6, stats.bigrams);
+        assert_eq!(3, stats.same_finger_bigrams);
+        assert!((0.5 - stats.same_finger_bigram_rate()).abs() < 0.01);
+    }
+}
@@ -0,0 +1,175 @@
+use std::{collections::HashMap, time::Duration};
+
+/// Represents a single character typed during a typing test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keystroke {
+    /// The time since the start of the test.
+    pub elapsed: Duration,
+
+    /// The index of the character within the current word, where the separator comes after the
+    /// last character.
+    pub position: usize,
+
+    /// The character that should have been typed, or `None` if the word was already complete.
+    pub expected: Option<char>,
+
+    /// The character that was actually typed.
+    pub actual: char,
+}
+
+/// Represents the accumulated statistics for a single key.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyStats {
+    /// The number of times this key needed to be typed.
+    pub presses: u64,
+
+    /// The number of times something else was typed instead of this key.
+    pub errors: u64,
+
+    /// The total time taken to correctly type this key, measured from the previous keystroke.
+    pub total_latency: Duration,
+
+    /// The number of correct presses that have a latency.
+    pub timed_presses: u64,
+}
+
+impl Keystroke {
+    /// Creates a new keystroke.
+    pub fn new(elapsed: Duration, position: usize, expected: Option<char>, actual: char) -> Self {
+        Self {
+            elapsed,
+            position,
+            expected,
+            actual,
+        }
+    }
+
+    /// Checks whether the expected character was typed.
+    pub fn is_correct(&self) -> bool {
+        self.expected == Some(self.actual)
+    }
+}
+
+impl KeyStats {
+    /// Calculates the proportion of presses for this key that were errors, between 0 and 1.
+    pub fn error_rate(&self) -> f32 {
+        if self.presses == 0 {
+            0.0
+        } else {
+            self.errors as f32 / self.presses as f32
+        }
+    }
+
+    /// Calculates the average time taken to correctly type this key.
+    pub fn average_latency(&self) -> Option<Duration> {
+        if self.timed_presses == 0 {
+            None
+        } else {
+            Some(self.total_latency / self.timed_presses as u32)
+        }
+    }
+
+    /// Adds the statistics from another set of key statistics.
+    pub fn merge(&mut self, other: &KeyStats) {
+        self.presses += other.presses;
+        self.errors += other.errors;
+        self.total_latency += other.total_latency;
+        self.timed_presses += other.timed_presses;
+    }
+}
+
+/// Calculates the statistics for each expected character from a sequence of keystrokes.
+///
+/// The latency of a keystroke is the time since the previous keystroke, so the first keystroke
+/// is not timed.
+pub fn key_stats(keystrokes: &[Keystroke]) -> HashMap<char, KeyStats> {
+    let mut stats: HashMap<char, KeyStats> = HashMap::new();
+    let mut previous: Option<&Keystroke> = None;
+
+    for keystroke in keystrokes {
+        if let Some(expected) = keystroke.expected {
+            let key = stats.entry(expected).or_default();
+            key.presses += 1;
+
+            if !keystroke.is_correct() {
+                key.errors += 1;
+            } else if let Some(previous) = previous {
+                key.total_latency += keystroke.elapsed - previous.elapsed;
+                key.timed_presses += 1;
+            }
+        }
+
+        previous = Some(keystroke);
+    }
+
+    stats
+}
+
+/// Combines the statistics for each key from several sources.
+pub fn merge_key_stats<'a>(
+    sources: impl IntoIterator<Item = &'a HashMap<char, KeyStats>>,
+) -> HashMap<char, KeyStats> {
+    let mut merged: HashMap<char, KeyStats> = HashMap::new();
+
+    for source in sources {
+        for (c, stats) in source {
+            merged.entry(*c).or_default().merge(stats);
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keystroke(millis: u64, expected: Option<char>, actual: char) -> Keystroke {
+        Keystroke::new(Duration::from_millis(millis), 0, expected, actual)
+    }
+
+    #[test]
+    fn key_stats_counts_errors_for_expected_key() {
+        let stats = key_stats(&[
+            keystroke(0, Some('a'), 'a'),
+            keystroke(100, Some('b'), 'v'),
+            keystroke(200, None, 'b'),
+        ]);
+
+        assert_eq!(1, stats[&'a'].presses);
+        assert_eq!(0, stats[&'a'].errors);
+        assert_eq!(1, stats[&'b'].presses);
+        assert_eq!(1, stats[&'b'].errors);
+        assert!(!stats.contains_key(&'v'));
+    }
+
+    #[test]
+    fn key_stats_times_correct_keystrokes_from_previous() {
+        let stats = key_stats(&[
+            keystroke(0, Some('a'), 'a'),
+            keystroke(150, Some('a'), 'a'),
+            keystroke(200, Some('a'), 'x'),
+            keystroke(450, Some('a'), 'a'),
+        ]);
+
+        assert_eq!(2, stats[&'a'].timed_presses);
+        assert_eq!(
+            Some(Duration::from_millis(200)),
+            stats[&'a'].average_latency()
+        );
+        assert!((0.25 - stats[&'a'].error_rate()).abs() < 0.01);
+    }
+
+    #[test]
+    fn merge_key_stats_adds_sources() {
+        let first = key_stats(&[keystroke(0, Some('a'), 'b')]);
+        let second = key_stats(&[keystroke(0, Some('a'), 'a')]);
+
+        let merged = merge_key_stats(vec![&first, &second]);
+
+        assert_eq!(2, merged[&'a'].presses);
+        assert_eq!(1, merged[&'a'].errors);
+    }
+}
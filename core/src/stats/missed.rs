@@ -0,0 +1,119 @@
+use std::cmp::Reverse;
+
+use super::MissedWord;
+
+/// Represents every missed attempt at the same expected word in a typing test.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MissedWordGroup {
+    pub expected: String,
+
+    /// What was typed for each attempt at the word (in the order they were typed).
+    pub attempts: Vec<String>,
+}
+
+/// Represents how a single character in a missed word compares with the expected word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharDiff {
+    /// The expected character was typed.
+    Correct(char),
+
+    /// A different character was typed instead of the expected character.
+    Incorrect(char),
+
+    /// The expected character was not typed, since the word ended early.
+    Missing(char),
+
+    /// A character was typed after the end of the expected word.
+    Extra(char),
+}
+
+impl MissedWordGroup {
+    /// Gets the number of times the word was missed.
+    pub fn count(&self) -> usize {
+        self.attempts.len()
+    }
+}
+
+/// Groups missed words by the expected word, from the most to the least frequently missed.
+///
+/// Words that were missed equally often are kept in the order they were first missed.
+pub fn group_missed_words(missed_words: &[MissedWord]) -> Vec<MissedWordGroup> {
+    let mut groups: Vec<MissedWordGroup> = Vec::new();
+
+    for missed_word in missed_words {
+        match groups
+            .iter_mut()
+            .find(|g| g.expected == missed_word.expected)
+        {
+            Some(group) => group.attempts.push(missed_word.actual.clone()),
+            None => groups.push(MissedWordGroup {
+                expected: missed_word.expected.clone(),
+                attempts: vec![missed_word.actual.clone()],
+            }),
+        }
+    }
+
+    // NOTE: Sorting is stable, so ties keep the order they were first missed
+    groups.sort_by_key(|g| Reverse(g.count()));
+    groups
+}
+
+/// Compares what was typed with the expected word, character by character.
+pub fn char_diff(expected: &str, actual: &str) -> Vec<CharDiff> {
+    let mut expected_chars = expected.chars();
+    let mut actual_chars = actual.chars();
+    let mut diff = Vec::new();
+
+    loop {
+        diff.push(match (expected_chars.next(), actual_chars.next()) {
+            (Some(e), Some(a)) if e == a => CharDiff::Correct(a),
+            (Some(_), Some(a)) => CharDiff::Incorrect(a),
+            (Some(e), None) => CharDiff::Missing(e),
+            (None, Some(a)) => CharDiff::Extra(a),
+            (None, None) => break,
+        });
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_missed_words_sorts_by_frequency() {
+        let missed_words = vec![
+            MissedWord::new("the", "teh"),
+            MissedWord::new("and", "adn"),
+            MissedWord::new("and", "an"),
+            MissedWord::new("of", "fo"),
+        ];
+
+        let groups = group_missed_words(&missed_words);
+        let summary: Vec<_> = groups
+            .iter()
+            .map(|g| (g.expected.as_str(), g.count()))
+            .collect();
+
+        assert_eq!(vec![("and", 2), ("the", 1), ("of", 1)], summary);
+        assert_eq!(vec!["adn", "an"], groups[0].attempts);
+    }
+
+    #[test]
+    fn char_diff_marks_each_character() {
+        assert_eq!(
+            vec![
+                CharDiff::Correct('c'),
+                CharDiff::Incorrect('u'),
+                CharDiff::Missing('t'),
+            ],
+            char_diff("cat", "cu")
+        );
+
+        assert_eq!(
+            vec![CharDiff::Correct('a'), CharDiff::Extra('a'),],
+            char_diff("a", "aa")
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::HashMap, time::Duration};
+
+use super::keys::Keystroke;
+
+/// Represents the accumulated statistics for a sequence of characters, such as a bigram.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NgramStats {
+    /// The number of times this sequence needed to be typed.
+    pub occurrences: u64,
+
+    /// The number of times this sequence was typed with at least one error.
+    pub errors: u64,
+
+    /// The total time taken to correctly type this sequence, from its first to its last character.
+    pub total_time: Duration,
+
+    /// The number of correct occurrences that have been timed.
+    pub timed_occurrences: u64,
+
+    /// The number of characters in this sequence.
+    pub length: usize,
+}
+
+impl NgramStats {
+    /// Calculates the proportion of occurrences that had errors, between 0 and 1.
+    pub fn error_rate(&self) -> f32 {
+        if self.occurrences == 0 {
+            0.0
+        } else {
+            self.errors as f32 / self.occurrences as f32
+        }
+    }
+
+    /// Calculates the average time between consecutive characters in this sequence.
+    pub fn average_time(&self) -> Option<Duration> {
+        if self.timed_occurrences == 0 || self.length < 2 {
+            None
+        } else {
+            Some(self.total_time / (self.timed_occurrences as u32 * (self.length as u32 - 1)))
+        }
+    }
+}
+
+/// Calculates the statistics for each sequence of `n` expected characters from a sequence of
+/// keystrokes.
+///
+/// Only sequences typed within a single word are included, so sequences containing separators or
+/// keystrokes that were later corrected are skipped.
+pub fn ngram_stats(keystrokes: &[Keystroke], n: usize) -> HashMap<String, NgramStats> {
+    let mut stats: HashMap<String, NgramStats> = HashMap::new();
+    if n < 2 {
+        return stats;
+    }
+
+    for window in keystrokes.windows(n) {
+        let is_consecutive = window
+            .windows(2)
+            .all(|pair| pair[1].position == pair[0].position + 1);
+
+        let ngram: Option<String> = window
+            .iter()
+            .map(|k| k.expected.filter(|c| !c.is_whitespace()))
+            .collect();
+
+        let ngram = match ngram {
+            Some(ngram) if is_consecutive => ngram,
+            _ => continue,
+        };
+
+        let entry = stats.entry(ngram).or_default();
+        entry.occurrences += 1;
+        entry.length = n;
+
+        if window.iter().all(Keystroke::is_correct) {
+            entry.total_time += window[n - 1].elapsed - window[0].elapsed;
+            entry.timed_occurrences += 1;
+        } else {
+            entry.errors += 1;
+        }
+    }
+
+    stats
+}
+
+/// Finds the sequences with the highest average time between characters, from slowest to fastest.
+///
+/// Sequences that were never typed correctly are not included.
+pub fn slowest_ngrams(
+    stats: &HashMap<String, NgramStats>,
+    count: usize,
+) -> Vec<(&str, NgramStats)> {
+    let mut timed: Vec<_> = stats
+        .iter()
+        .filter_map(|(ngram, s)| s.average_time().map(|time| (ngram.as_str(), *s, time)))
+        .collect();
+
+    // Break ties alphabetically so that the order is stable
+    timed.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(b.0)));
+
+    timed
+        .into_iter()
+        .take(count)
+        .map(|(ngram, s, _)| (ngram, s))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keystroke(millis: u64, position: usize, expected: char, actual: char) -> Keystroke {
+        Keystroke::new(
+            Duration::from_millis(millis),
+            position,
+            Some(expected),
+            actual,
+        )
+    }
+
+    #[test]
+    fn ngram_stats_times_bigrams_within_words() {
+        let stats = ngram_stats(
+            &[
+                keystroke(0, 0, 't', 't'),
+                keystroke(100, 1, 'h', 'h'),
+                keystroke(300, 2, 'e', 'e'),
+                keystroke(400, 3, ' ', ' '),
+                keystroke(500, 0, 't', 't'),
+                keystroke(550, 1, 'h', 'h'),
+            ],
+            2,
+        );
+
+        assert_eq!(2, stats.len());
+        assert_eq!(2, stats["th"].occurrences);
+        assert_eq!(Some(Duration::from_millis(75)), stats["th"].average_time());
+        assert_eq!(Some(Duration::from_millis(200)), stats["he"].average_time());
+        assert!(!stats.contains_key("e "));
+    }
+
+    #[test]
+    fn ngram_stats_counts_errors_and_skips_corrections() {
+        let stats = ngram_stats(
+            &[
+                keystroke(0, 0, 'c', 'c'),
+                keystroke(100, 1, 'a', 'x'),
+                keystroke(200, 1, 'a', 'a'),
+                keystroke(300, 2, 't', 't'),
+            ],
+            2,
+        );
+
+        assert_eq!(1, stats["ca"].errors);
+        assert_eq!(None, stats["ca"].average_time());
+        assert_eq!(0, stats["at"].errors);
+        assert!(!stats.contains_key("aa"));
+    }
+
+    #[test]
+    fn ngram_stats_averages_trigrams_per_character() {
+        let stats = ngram_stats(
+            &[
+                keystroke(0, 0, 'a', 'a'),
+                keystroke(100, 1, 'n', 'n'),
+                keystroke(300, 2, 'd', 'd'),
+            ],
+            3,
+        );
+
+        assert_eq!(
+            Some(Duration::from_millis(150)),
+            stats["and"].average_time()
+        );
+    }
+
+    #[test]
+    fn slowest_ngrams_sorts_by_average_time() {
+        let stats = ngram_stats(
+            &[
+                keystroke(0, 0, 'a', 'a'),
+                keystroke(100, 1, 'b', 'b'),
+                keystroke(400, 2, 'c', 'c'),
+                keystroke(600, 3, 'd', 'd'),
+            ],
+            2,
+        );
+
+        let slowest: Vec<_> = slowest_ngrams(&stats, 2)
+            .into_iter()
+            .map(|(ngram, _)| ngram)
+            .collect();
+
+        assert_eq!(vec!["bc", "cd"], slowest);
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt::Display;
+
+use super::{TestCheckpoint, TestStats};
+
+/// Represents how WPM is calculated from the characters typed during a test.
+///
+/// Every method uses 1 WPM = 5 CPM, where separators count as characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WpmMethod {
+    /// Only correct characters count towards the WPM.
+    Standard,
+
+    /// The gross WPM, minus one word per minute for each uncorrected error.
+    Net,
+
+    /// All typed characters count towards the WPM, whether they were correct or not.
+    Gross,
+}
+
+/// Represents how accuracy is calculated during a test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AccuracyMethod {
+    /// The proportion of submitted characters that were correct.
+    Characters,
+
+    /// The proportion of keystrokes that were correct, including mistakes that were corrected.
+    Keystrokes,
+
+    /// The proportion of submitted words that were correct.
+    Words,
+}
+
+/// Represents the strategy used to score a typing test.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scoring {
+    pub wpm: WpmMethod,
+    pub accuracy: AccuracyMethod,
+}
+
+impl WpmMethod {
+    /// An array of all the WPM methods.
+    pub const ALL_METHODS: [WpmMethod; 3] = [WpmMethod::Standard, WpmMethod::Net, WpmMethod::Gross];
+}
+
+impl AccuracyMethod {
+    /// An array of all the accuracy methods.
+    pub const ALL_METHODS: [AccuracyMethod; 3] = [
+        AccuracyMethod::Characters,
+        AccuracyMethod::Keystrokes,
+        AccuracyMethod::Words,
+    ];
+}
+
+impl Scoring {
+    /// Calculates the WPM for a checkpoint, or `None` if no time has elapsed.
+    pub fn wpm(&self, checkpoint: &TestCheckpoint) -> Option<u64> {
+        match self.wpm {
+            WpmMethod::Standard => checkpoint.effective_wpm(),
+            WpmMethod::Net => {
+                let raw_wpm = checkpoint.raw_wpm()?;
+                let minutes = checkpoint.elapsed.as_secs_f32() / 60.0;
+                let penalty = checkpoint.incorrect_chars as f32 / minutes;
+                Some((raw_wpm as f32 - penalty).max(0.0) as u64)
+            }
+            WpmMethod::Gross => checkpoint.raw_wpm(),
+        }
+    }
+
+    /// Calculates the accuracy for a checkpoint in a test as a percentage, or `None` if nothing
+    /// has been typed.
+    ///
+    /// Keystroke accuracy always uses every keystroke in the test, since keystrokes aren't tracked
+    /// per checkpoint.
+    pub fn accuracy(&self, stats: &TestStats, checkpoint: &TestCheckpoint) -> Option<f32> {
+        match self.accuracy {
+            AccuracyMethod::Characters => checkpoint.accuracy(),
+            AccuracyMethod::Keystrokes => {
+                let keystrokes = stats.get_keystrokes();
+                let correct = keystrokes.iter().filter(|k| k.is_correct()).count();
+                percentage(correct as u64, keystrokes.len() as u64)
+            }
+            AccuracyMethod::Words => percentage(
+                checkpoint.correct_words,
+                checkpoint.correct_words + checkpoint.incorrect_words,
+            ),
+        }
+    }
+}
+
+impl Default for WpmMethod {
+    fn default() -> Self {
+        WpmMethod::Standard
+    }
+}
+
+impl Default for AccuracyMethod {
+    fn default() -> Self {
+        AccuracyMethod::Characters
+    }
+}
+
+impl Display for WpmMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            WpmMethod::Standard => f.write_str("Standard"),
+            WpmMethod::Net => f.write_str("Net (Error Penalty)"),
+            WpmMethod::Gross => f.write_str("Gross"),
+        }
+    }
+}
+
+impl Display for AccuracyMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            AccuracyMethod::Characters => f.write_str("Characters"),
+            AccuracyMethod::Keystrokes => f.write_str("Keystrokes"),
+            AccuracyMethod::Words => f.write_str("Words"),
+        }
+    }
+}
+
+impl Display for Scoring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} WPM, {} Accuracy", self.wpm, self.accuracy)
+    }
+}
+
+/// Calculates a percentage, or `None` if the total is empty.
+fn percentage(count: u64, total: u64) -> Option<f32> {
+    if total == 0 {
+        None
+    } else {
+        Some(count as f32 / total as f32 * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use super::*;
+
+    fn checkpoint(correct_chars: u64, incorrect_chars: u64) -> TestCheckpoint {
+        TestCheckpoint {
+            elapsed: Duration::from_secs(60),
+
+            correct_chars,
+            incorrect_chars,
+            correct_words: 3,
+            incorrect_words: 1,
+            blocked_attempts: 0,
+        }
+    }
+
+    #[rstest(
+        method,
+        expected_wpm,
+        case(WpmMethod::Standard, 20),
+        case(WpmMethod::Net, 4),
+        case(WpmMethod::Gross, 24)
+    )]
+    fn wpm(method: WpmMethod, expected_wpm: u64) {
+        let scoring = Scoring {
+            wpm: method,
+            ..Scoring::default()
+        };
+
+        assert_eq!(Some(expected_wpm), scoring.wpm(&checkpoint(100, 20)));
+    }
+
+    #[test]
+    fn net_wpm_is_never_negative() {
+        let scoring = Scoring {
+            wpm: WpmMethod::Net,
+            ..Scoring::default()
+        };
+
+        assert_eq!(Some(0), scoring.wpm(&checkpoint(10, 40)));
+    }
+
+    #[test]
+    fn keystroke_accuracy_includes_corrected_mistakes() {
+        let mut stats = TestStats::new();
+        stats.record_input("cat", "", "cx");
+        stats.record_input("cat", "cx", "c");
+        stats.record_input("cat", "c", "cat");
+        stats.submit_word("cat", "cat");
+        stats.checkpoint();
+
+        let checkpoint = *stats.get_latest_checkpoint().unwrap();
+        let scoring = Scoring {
+            accuracy: AccuracyMethod::Keystrokes,
+            ..Scoring::default()
+        };
+
+        assert!((100.0 - checkpoint.accuracy().unwrap()).abs() < 0.01);
+        assert!((80.0 - scoring.accuracy(&stats, &checkpoint).unwrap()).abs() < 0.01);
+    }
+
+    #[test]
+    fn word_accuracy() {
+        let scoring = Scoring {
+            accuracy: AccuracyMethod::Words,
+            ..Scoring::default()
+        };
+
+        let accuracy = scoring.accuracy(&TestStats::new(), &checkpoint(100, 20));
+        assert!((75.0 - accuracy.unwrap()).abs() < 0.01);
+    }
+
+    #[rstest(
+        method,
+        case(AccuracyMethod::Characters),
+        case(AccuracyMethod::Keystrokes),
+        case(AccuracyMethod::Words)
+    )]
+    fn accuracy_is_undefined_when_nothing_was_typed(method: AccuracyMethod) {
+        let scoring = Scoring {
+            accuracy: method,
+            ..Scoring::default()
+        };
+
+        let empty = TestCheckpoint {
+            correct_words: 0,
+            incorrect_words: 0,
+            ..checkpoint(0, 0)
+        };
+
+        assert_eq!(None, scoring.accuracy(&TestStats::new(), &empty));
+    }
+}
@@ -1,31 +1,91 @@
+use std::collections::VecDeque;
+
+pub mod code;
+pub mod custom;
+pub mod drill;
+pub mod letter_set;
+pub mod markov;
+pub mod passage;
 pub mod random;
 
+/// The number of columns that a tab character occupies when displayed.
+pub const TAB_WIDTH: usize = 4;
+
 /// Represents the different statuses a word could be in during a typing test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum WordStatus {
     NotTyped,
     Correct,
     Incorrect,
 }
 
+/// Represents the whitespace that needs to be typed to submit a word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Separator {
+    Space,
+    Newline,
+}
+
 /// Represents a word that is intended to be displayed during a typing test.
 pub struct DisplayedWord {
     pub word: String,
     pub status: WordStatus,
+    pub separator: Separator,
+}
+
+impl Separator {
+    /// Gets the character that is typed for this separator.
+    pub fn as_char(self) -> char {
+        match self {
+            Separator::Space => ' ',
+            Separator::Newline => '\n',
+        }
+    }
 }
 
 impl DisplayedWord {
     pub fn new(word: impl Into<String>) -> Self {
+        Self::with_separator(word, Separator::Space)
+    }
+
+    /// Creates a new displayed word which is submitted using the provided separator.
+    pub fn with_separator(word: impl Into<String>, separator: Separator) -> Self {
         DisplayedWord {
             word: word.into(),
             status: WordStatus::NotTyped,
+            separator,
         }
     }
+
+    /// Gets the number of columns this word occupies when displayed, expanding any tabs.
+    pub fn display_len(&self) -> usize {
+        self.word
+            .chars()
+            .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+            .sum()
+    }
+
+    /// Gets the number of columns this word occupies when displayed, including the marker shown
+    /// after words submitted with a newline.
+    pub fn marked_len(&self) -> usize {
+        let marker = if self.separator == Separator::Newline {
+            1
+        } else {
+            0
+        };
+
+        self.display_len() + marker
+    }
 }
 
 /// Common trait for all word generators.
 pub trait WordGenerator {
     /// Fills a line with words, respecting the maximum number of characters.
     ///
+    /// A word that is submitted with a newline must be the last word in the line, since the
+    /// newline is displayed as a line break.
+    ///
     /// Requires `&mut self` since some word generators may need to update their state,
     /// e.g. a word generator that replicates a passage of text.
     fn fill_line(&mut self, line: &mut Vec<DisplayedWord>, max_chars: usize);
@@ -36,3 +96,173 @@ pub trait WordGenerator {
     /// Prepares this word generator for the next test.
     fn next_test(&mut self);
 }
+
+/// Splits text into words, where the last word on each line is submitted with a newline.
+///
+/// Blank lines are skipped. If `keep_indentation` is set, leading whitespace is converted into tabs
+/// at the start of the first word on each line, where each tab or [`TAB_WIDTH`] spaces is one
+/// level of indentation; otherwise, it is discarded.
+pub(crate) fn tokenize_lines(text: &str, keep_indentation: bool) -> Vec<DisplayedWord> {
+    let mut tokens = Vec::new();
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let indent = if keep_indentation {
+            let leading = &line[..line.len() - trimmed.len()];
+            let tabs = leading.matches('\t').count();
+            let spaces = leading.matches(' ').count();
+            "\t".repeat(tabs + spaces / TAB_WIDTH)
+        } else {
+            String::new()
+        };
+
+        let mut words = trimmed.split_whitespace().peekable();
+        let mut first = true;
+        while let Some(word) = words.next() {
+            let separator = if words.peek().is_some() {
+                Separator::Space
+            } else {
+                Separator::Newline
+            };
+
+            if first {
+                tokens.push(DisplayedWord::with_separator(
+                    format!("{}{}", indent, word),
+                    separator,
+                ));
+                first = false;
+            } else {
+                tokens.push(DisplayedWord::with_separator(word, separator));
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Continues filling a line using words from a queue, respecting the maximum number of characters.
+///
+/// Returns `true` if the line is complete, i.e. the next word would not fit or a word submitted with
+/// a newline was reached. Returns `false` if the queue ran out of words first.
+pub(crate) fn fill_line_from_queue(
+    queue: &mut VecDeque<DisplayedWord>,
+    line: &mut Vec<DisplayedWord>,
+    max_chars: usize,
+) -> bool {
+    // NOTE: +1 to length due to the separator between words
+    let mut chars = line.iter().map(|w| w.marked_len() + 1).sum::<usize>();
+
+    while let Some(word) = queue.front() {
+        if !line.is_empty() && chars + word.marked_len() > max_chars {
+            return true;
+        }
+
+        let word = queue.pop_front().unwrap();
+        let separator = word.separator;
+
+        chars += word.marked_len() + 1;
+        line.push(word);
+
+        if separator == Separator::Newline {
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_lines_converts_indentation_and_line_endings() {
+        let tokens = tokenize_lines("fn main() {\n\n    x += 1;\n}", true);
+        let words: Vec<_> = tokens
+            .iter()
+            .map(|t| (t.word.as_str(), t.separator))
+            .collect();
+
+        assert_eq!(
+            vec![
+                ("fn", Separator::Space),
+                ("main()", Separator::Space),
+                ("{", Separator::Newline),
+                ("\tx", Separator::Space),
+                ("+=", Separator::Space),
+                ("1;", Separator::Newline),
+                ("}", Separator::Newline),
+            ],
+            words
+        );
+    }
+
+    #[test]
+    fn tokenize_lines_counts_tabs_as_indentation() {
+        let tokens = tokenize_lines("if x:\n\ty = 1\n\t\treturn\n\t    pass", true);
+        let words: Vec<_> = tokens.iter().map(|t| t.word.as_str()).collect();
+
+        assert_eq!(
+            vec!["if", "x:", "\ty", "=", "1", "\t\treturn", "\t\tpass"],
+            words
+        );
+    }
+
+    #[test]
+    fn tokenize_lines_discards_indentation_for_prose() {
+        let tokens = tokenize_lines("  Call me Ishmael.", false);
+
+        assert_eq!("Call", tokens[0].word);
+        assert_eq!(Separator::Newline, tokens[2].separator);
+    }
+
+    #[test]
+    fn fill_line_from_queue_wraps_long_lines() {
+        let mut queue: VecDeque<_> = tokenize_lines("aaaa bbbb cccc", false).into();
+        let mut line = Vec::new();
+
+        assert!(fill_line_from_queue(&mut queue, &mut line, 10));
+        assert_eq!(2, line.len());
+        assert_eq!(Separator::Space, line[1].separator);
+
+        line.clear();
+        assert!(fill_line_from_queue(&mut queue, &mut line, 10));
+        assert_eq!(1, line.len());
+        assert_eq!(Separator::Newline, line[0].separator);
+    }
+
+    #[test]
+    fn fill_line_from_queue_counts_newline_marker() {
+        let mut queue: VecDeque<_> = tokenize_lines("aaaa bbbb", false).into();
+        let mut line = Vec::new();
+
+        // "aaaa bbbb" fits in 9 columns, but not with the marker after "bbbb"
+        assert!(fill_line_from_queue(&mut queue, &mut line, 9));
+        assert_eq!(1, line.len());
+
+        line.clear();
+        assert!(fill_line_from_queue(&mut queue, &mut line, 9));
+        assert_eq!(Separator::Newline, line[0].separator);
+    }
+
+    #[test]
+    fn fill_line_from_queue_stops_at_newline() {
+        let mut queue: VecDeque<_> = tokenize_lines("a b\nc", false).into();
+        let mut line = Vec::new();
+
+        assert!(fill_line_from_queue(&mut queue, &mut line, 80));
+        assert_eq!(2, line.len());
+
+        line.clear();
+        assert!(fill_line_from_queue(&mut queue, &mut line, 80));
+        assert_eq!(
+            vec!["c"],
+            line.iter().map(|w| w.word.as_str()).collect::<Vec<_>>()
+        );
+        assert!(!fill_line_from_queue(&mut queue, &mut line, 80));
+    }
+}
@@ -0,0 +1,139 @@
+use std::{collections::VecDeque, fmt::Display};
+
+use rand::{
+    prelude::{SliceRandom, SmallRng},
+    Rng, SeedableRng,
+};
+
+use crate::word_pools::code_snippets;
+
+use super::{fill_line_from_queue, tokenize_lines, DisplayedWord, WordGenerator};
+
+/// Represents the programming languages that code snippets are available for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodeLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    C,
+}
+
+/// Implementation of an infinite word generator using random code snippets.
+///
+/// Each line of a snippet is split into tokens at spaces, with the last token on a line being
+/// submitted with a newline. Indentation is converted into tabs at the start of the first token.
+pub struct CodeWordGenerator {
+    snippets: Vec<String>,
+    pending: VecDeque<DisplayedWord>,
+    rng: SmallRng,
+    rng_seed: u64,
+}
+
+impl CodeLanguage {
+    /// An array of all the available code languages.
+    pub const ALL_LANGUAGES: [CodeLanguage; 4] = [
+        CodeLanguage::Rust,
+        CodeLanguage::Python,
+        CodeLanguage::JavaScript,
+        CodeLanguage::C,
+    ];
+
+    /// Gets the built-in code snippets for this language.
+    pub fn snippets(self) -> &'static [&'static str] {
+        match self {
+            CodeLanguage::Rust => &code_snippets::RUST,
+            CodeLanguage::Python => &code_snippets::PYTHON,
+            CodeLanguage::JavaScript => &code_snippets::JAVASCRIPT,
+            CodeLanguage::C => &code_snippets::C,
+        }
+    }
+}
+
+impl Default for CodeLanguage {
+    fn default() -> Self {
+        CodeLanguage::Rust
+    }
+}
+
+impl Display for CodeLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            CodeLanguage::Rust => f.write_str("Rust"),
+            CodeLanguage::Python => f.write_str("Python"),
+            CodeLanguage::JavaScript => f.write_str("JavaScript"),
+            CodeLanguage::C => f.write_str("C"),
+        }
+    }
+}
+
+impl CodeWordGenerator {
+    pub fn new(snippets: Vec<String>) -> Self {
+        let rng_seed = rand::thread_rng().gen::<u64>();
+        let rng = SmallRng::seed_from_u64(rng_seed);
+
+        Self {
+            snippets,
+            pending: VecDeque::new(),
+            rng,
+            rng_seed,
+        }
+    }
+
+    /// Queues up the tokens for another random snippet.
+    fn queue_snippet(&mut self) {
+        let snippet = self
+            .snippets
+            .choose(&mut self.rng)
+            .expect("Snippet pool is empty!");
+
+        self.pending.extend(tokenize_lines(snippet, true));
+    }
+}
+
+impl From<CodeLanguage> for CodeWordGenerator {
+    fn from(language: CodeLanguage) -> Self {
+        Self::new(language.snippets().iter().map(|s| s.to_string()).collect())
+    }
+}
+
+impl WordGenerator for CodeWordGenerator {
+    fn fill_line(&mut self, line: &mut Vec<DisplayedWord>, max_chars: usize) {
+        line.clear();
+
+        while !fill_line_from_queue(&mut self.pending, line, max_chars) {
+            self.queue_snippet();
+        }
+    }
+
+    fn redo(&mut self) {
+        self.rng = SmallRng::seed_from_u64(self.rng_seed);
+        self.pending.clear();
+    }
+
+    fn next_test(&mut self) {
+        self.rng_seed = rand::thread_rng().gen::<u64>();
+        self.rng = SmallRng::seed_from_u64(self.rng_seed);
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_generators::Separator;
+
+    use super::*;
+
+    #[test]
+    fn fill_line_stops_at_end_of_code_line() {
+        let mut generator = CodeWordGenerator::new(vec![String::from("a b\nc")]);
+        let mut line = Vec::new();
+
+        generator.fill_line(&mut line, 80);
+        assert_eq!(2, line.len());
+        assert_eq!(Separator::Newline, line[1].separator);
+
+        generator.fill_line(&mut line, 80);
+        assert_eq!(1, line.len());
+        assert_eq!("c", line[0].word);
+    }
+}
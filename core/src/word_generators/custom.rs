@@ -0,0 +1,169 @@
+use std::collections::VecDeque;
+
+use rand::{
+    prelude::{SliceRandom, SmallRng},
+    Rng, SeedableRng,
+};
+
+use super::{fill_line_from_queue, tokenize_lines, DisplayedWord, WordGenerator};
+
+/// Represents the options for how a custom text is turned into a typing test.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CustomTextOptions {
+    /// Whether the text starts again once it has been typed.
+    pub repeat: bool,
+
+    /// Whether the words in the text are shuffled. Shuffled words are always separated by spaces.
+    pub shuffle: bool,
+
+    /// The maximum number of words to take from the text.
+    pub word_limit: Option<usize>,
+}
+
+/// Implementation of a word generator using a custom text provided by the user.
+///
+/// Unless the text is repeated, it runs out once all of its words have been generated, after
+/// which any lines that are filled are left empty.
+pub struct CustomWordGenerator {
+    text: String,
+    options: CustomTextOptions,
+    pending: VecDeque<DisplayedWord>,
+    exhausted: bool,
+    rng: SmallRng,
+    rng_seed: u64,
+}
+
+impl CustomWordGenerator {
+    pub fn new(text: impl Into<String>, options: CustomTextOptions) -> Self {
+        let rng_seed = rand::thread_rng().gen::<u64>();
+        let rng = SmallRng::seed_from_u64(rng_seed);
+
+        Self {
+            text: text.into(),
+            options,
+            pending: VecDeque::new(),
+            exhausted: false,
+            rng,
+            rng_seed,
+        }
+    }
+
+    /// Queues up the words for another pass through the text.
+    ///
+    /// Returns `false` if no words could be queued, i.e. the text is empty or has already been
+    /// used without repeating.
+    fn queue_text(&mut self) -> bool {
+        if self.exhausted {
+            return false;
+        }
+
+        let mut words = if self.options.shuffle {
+            let mut words: Vec<_> = self
+                .text
+                .split_whitespace()
+                .map(DisplayedWord::new)
+                .collect();
+
+            words.shuffle(&mut self.rng);
+            words
+        } else {
+            tokenize_lines(&self.text, false)
+        };
+
+        if let Some(limit) = self.options.word_limit {
+            words.truncate(limit);
+        }
+
+        self.exhausted = !self.options.repeat || words.is_empty();
+        self.pending.extend(words);
+
+        !self.pending.is_empty()
+    }
+
+    /// Resets the state for a new pass through the text.
+    fn reset(&mut self) {
+        self.rng = SmallRng::seed_from_u64(self.rng_seed);
+        self.pending.clear();
+        self.exhausted = false;
+    }
+}
+
+impl WordGenerator for CustomWordGenerator {
+    fn fill_line(&mut self, line: &mut Vec<DisplayedWord>, max_chars: usize) {
+        line.clear();
+
+        while !fill_line_from_queue(&mut self.pending, line, max_chars) {
+            if !self.queue_text() {
+                break;
+            }
+        }
+    }
+
+    fn redo(&mut self) {
+        self.reset();
+    }
+
+    fn next_test(&mut self) {
+        self.rng_seed = rand::thread_rng().gen::<u64>();
+        self.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill_words(generator: &mut CustomWordGenerator) -> Vec<String> {
+        let mut line = Vec::new();
+        generator.fill_line(&mut line, 80);
+        line.into_iter().map(|w| w.word).collect()
+    }
+
+    #[test]
+    fn text_runs_out_without_repeat() {
+        let mut generator = CustomWordGenerator::new("a b c", CustomTextOptions::default());
+
+        assert_eq!(vec!["a", "b", "c"], fill_words(&mut generator));
+        assert!(fill_words(&mut generator).is_empty());
+    }
+
+    #[test]
+    fn text_repeats_up_to_word_limit() {
+        let options = CustomTextOptions {
+            repeat: true,
+            word_limit: Some(2),
+            ..CustomTextOptions::default()
+        };
+        let mut generator = CustomWordGenerator::new("a b c", options);
+
+        assert_eq!(
+            vec!["a", "b", "a", "b"],
+            fill_words(&mut generator)[..4].to_vec()
+        );
+    }
+
+    #[test]
+    fn shuffled_text_is_reproducible_on_redo() {
+        let options = CustomTextOptions {
+            shuffle: true,
+            ..CustomTextOptions::default()
+        };
+        let mut generator = CustomWordGenerator::new("a b c d e f g h", options);
+
+        let words = fill_words(&mut generator);
+        generator.redo();
+
+        assert_eq!(words, fill_words(&mut generator));
+    }
+
+    #[test]
+    fn empty_text_leaves_line_empty() {
+        let options = CustomTextOptions {
+            repeat: true,
+            ..CustomTextOptions::default()
+        };
+        let mut generator = CustomWordGenerator::new("  \n ", options);
+
+        assert!(fill_words(&mut generator).is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::VecDeque;
+
+use rand::{
+    prelude::{SliceRandom, SmallRng},
+    Rng, SeedableRng,
+};
+
+use crate::stats::{missed, MissedWord};
+
+use super::{fill_line_from_queue, DisplayedWord, WordGenerator};
+
+/// Implementation of a word generator for a short drill, which repeats a set of words a fixed
+/// number of times.
+///
+/// Each repetition goes through every word in a random order, so the words are interleaved rather
+/// than typed in runs. Once every repetition has been generated, any lines that are filled are
+/// left empty.
+pub struct DrillWordGenerator {
+    words: Vec<String>,
+    repetitions: usize,
+    pending: VecDeque<DisplayedWord>,
+    queued: bool,
+    rng: SmallRng,
+    rng_seed: u64,
+}
+
+impl DrillWordGenerator {
+    pub fn new(words: Vec<String>, repetitions: usize) -> Self {
+        let rng_seed = rand::thread_rng().gen::<u64>();
+        let rng = SmallRng::seed_from_u64(rng_seed);
+
+        Self {
+            words,
+            repetitions,
+            pending: VecDeque::new(),
+            queued: false,
+            rng,
+            rng_seed,
+        }
+    }
+
+    /// Creates a drill for the words that were missed during a typing test, where each distinct
+    /// word is repeated `repetitions` times.
+    pub fn from_missed_words(missed_words: &[MissedWord], repetitions: usize) -> Self {
+        let words = missed::group_missed_words(missed_words)
+            .into_iter()
+            .map(|group| group.expected)
+            .collect();
+
+        Self::new(words, repetitions)
+    }
+
+    /// Queues up every repetition of the drill.
+    fn queue_drill(&mut self) {
+        let mut previous: Option<String> = None;
+
+        for _ in 0..self.repetitions {
+            let mut round = self.words.clone();
+            round.shuffle(&mut self.rng);
+
+            // Avoid typing the same word twice in a row where one round meets the next
+            if round.len() > 1 && round.first() == previous.as_ref() {
+                let swap_with = self.rng.gen_range(1..round.len());
+                round.swap(0, swap_with);
+            }
+
+            previous = round.last().cloned();
+            self.pending
+                .extend(round.into_iter().map(DisplayedWord::new));
+        }
+
+        self.queued = true;
+    }
+
+    /// Resets the state for another run through the drill.
+    fn reset(&mut self) {
+        self.rng = SmallRng::seed_from_u64(self.rng_seed);
+        self.pending.clear();
+        self.queued = false;
+    }
+}
+
+impl WordGenerator for DrillWordGenerator {
+    fn fill_line(&mut self, line: &mut Vec<DisplayedWord>, max_chars: usize) {
+        line.clear();
+
+        if !self.queued {
+            self.queue_drill();
+        }
+
+        fill_line_from_queue(&mut self.pending, line, max_chars);
+    }
+
+    fn redo(&mut self) {
+        self.reset();
+    }
+
+    fn next_test(&mut self) {
+        self.rng_seed = rand::thread_rng().gen::<u64>();
+        self.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_words(generator: &mut DrillWordGenerator) -> Vec<String> {
+        let mut words = Vec::new();
+        let mut line = Vec::new();
+
+        loop {
+            generator.fill_line(&mut line, 20);
+            if line.is_empty() {
+                return words;
+            }
+
+            words.extend(line.drain(..).map(|w| w.word));
+        }
+    }
+
+    #[test]
+    fn drill_repeats_each_word_without_runs() {
+        let words = vec![String::from("the"), String::from("and"), String::from("of")];
+        let mut generator = DrillWordGenerator::new(words.clone(), 4);
+
+        let drill = all_words(&mut generator);
+
+        assert_eq!(12, drill.len());
+        for word in &words {
+            assert_eq!(4, drill.iter().filter(|w| *w == word).count());
+        }
+        assert!(drill.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn drill_is_reproducible_on_redo() {
+        let words = (0..10).map(|i| i.to_string()).collect();
+        let mut generator = DrillWordGenerator::new(words, 3);
+
+        let drill = all_words(&mut generator);
+        generator.redo();
+
+        assert_eq!(drill, all_words(&mut generator));
+    }
+
+    #[test]
+    fn drill_from_missed_words_uses_each_word_once_per_repetition() {
+        let missed_words = vec![
+            MissedWord::new("the", "teh"),
+            MissedWord::new("the", "hte"),
+            MissedWord::new("of", "fo"),
+        ];
+        let mut generator = DrillWordGenerator::from_missed_words(&missed_words, 2);
+
+        let mut drill = all_words(&mut generator);
+        drill.sort();
+
+        assert_eq!(vec!["of", "of", "the", "the"], drill);
+    }
+}
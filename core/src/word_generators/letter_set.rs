@@ -0,0 +1,291 @@
+use rand::{
+    prelude::{SliceRandom, SmallRng},
+    Rng, SeedableRng,
+};
+
+use crate::word_pools::default_english::DEFAULT_ENGLISH;
+
+use super::{DisplayedWord, WordGenerator};
+
+/// The number of real words needed before they are picked as often as they can be.
+const MIN_REAL_WORDS: usize = 20;
+
+/// The largest share of words that are real words, so that pseudo-words are always mixed in.
+const MAX_REAL_WORD_SHARE: f64 = 0.5;
+
+/// The letters that are treated as vowels when building pseudo-words.
+const VOWELS: [char; 5] = ['a', 'e', 'i', 'o', 'u'];
+
+/// Represents the options for generating words from a restricted set of letters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LetterSetOptions {
+    /// The minimum number of letters in each word.
+    pub min_length: usize,
+
+    /// The maximum number of letters in each word.
+    pub max_length: usize,
+
+    /// Whether real words that only use the letters are mixed in with the pseudo-words.
+    pub real_words: bool,
+}
+
+/// Implementation of an infinite word generator which only uses a restricted set of letters.
+///
+/// Pseudo-words alternate between vowels and consonants so that they can be pronounced, as long as
+/// the set has both. Real words from the word pool that only use the letters are mixed in, with
+/// more pseudo-words when only a few real words are available.
+pub struct LetterSetWordGenerator {
+    letters: Vec<char>,
+    vowels: Vec<char>,
+    consonants: Vec<char>,
+    real_words: Vec<String>,
+    options: LetterSetOptions,
+    rng: SmallRng,
+    rng_seed: u64,
+}
+
+impl Default for LetterSetOptions {
+    fn default() -> Self {
+        Self {
+            min_length: 2,
+            max_length: 6,
+            real_words: true,
+        }
+    }
+}
+
+impl LetterSetWordGenerator {
+    /// Creates a new generator for a set of letters, using the default English word pool for real
+    /// words.
+    pub fn new(letters: impl IntoIterator<Item = char>, options: LetterSetOptions) -> Self {
+        let word_pool = DEFAULT_ENGLISH.iter().map(|s| s.to_string()).collect();
+        Self::with_word_pool(letters, word_pool, options)
+    }
+
+    /// Creates a new generator for a set of letters, taking real words from the provided pool.
+    ///
+    /// Letters are converted to lowercase, and whitespace is ignored.
+    pub fn with_word_pool(
+        letters: impl IntoIterator<Item = char>,
+        word_pool: Vec<String>,
+        options: LetterSetOptions,
+    ) -> Self {
+        let rng_seed = rand::thread_rng().gen::<u64>();
+        let rng = SmallRng::seed_from_u64(rng_seed);
+
+        let mut unique_letters: Vec<char> = Vec::new();
+        for c in letters.into_iter().flat_map(char::to_lowercase) {
+            if !c.is_whitespace() && !unique_letters.contains(&c) {
+                unique_letters.push(c);
+            }
+        }
+
+        let (vowels, consonants) = unique_letters.iter().partition(|c| VOWELS.contains(c));
+
+        let max_length = options.max_length.max(options.min_length);
+        let real_words = if options.real_words {
+            word_pool
+                .into_iter()
+                .filter(|word| {
+                    let length = word.chars().count();
+                    length >= options.min_length
+                        && length <= max_length
+                        && word.chars().all(|c| unique_letters.contains(&c))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            letters: unique_letters,
+            vowels,
+            consonants,
+            real_words,
+            options,
+            rng,
+            rng_seed,
+        }
+    }
+
+    /// Picks the next word, which is either a real word or a pseudo-word.
+    ///
+    /// Returns `None` if there are no letters to build words from.
+    fn next_word(&mut self) -> Option<String> {
+        if self.letters.is_empty() {
+            return None;
+        }
+
+        let real_chance =
+            (self.real_words.len() as f64 / MIN_REAL_WORDS as f64).min(1.0) * MAX_REAL_WORD_SHARE;
+        if let Some(word) = self.real_words.choose(&mut self.rng) {
+            if self.rng.gen_bool(real_chance) {
+                return Some(word.clone());
+            }
+        }
+
+        Some(self.pseudo_word())
+    }
+
+    /// Builds a pseudo-word with a random length, alternating between vowels and consonants where
+    /// possible.
+    fn pseudo_word(&mut self) -> String {
+        let min_length = self.options.min_length.max(1);
+        let max_length = self.options.max_length.max(min_length);
+        let length = self.rng.gen_range(min_length..=max_length);
+
+        if self.vowels.is_empty() || self.consonants.is_empty() {
+            return (0..length)
+                .map(|_| *self.letters.choose(&mut self.rng).unwrap())
+                .collect();
+        }
+
+        // Words are more likely to start with a consonant
+        let mut vowel = self.rng.gen_bool(0.3);
+        let mut word = String::with_capacity(length);
+        for _ in 0..length {
+            let group = if vowel {
+                &self.vowels
+            } else {
+                &self.consonants
+            };
+            word.push(*group.choose(&mut self.rng).unwrap());
+            vowel = !vowel;
+        }
+
+        word
+    }
+}
+
+impl WordGenerator for LetterSetWordGenerator {
+    fn fill_line(&mut self, line: &mut Vec<DisplayedWord>, max_chars: usize) {
+        line.clear();
+
+        let mut word = match self.next_word() {
+            Some(word) => word,
+            None => return,
+        };
+        let mut chars = word.chars().count();
+
+        while chars < max_chars {
+            line.push(DisplayedWord::new(word));
+
+            // NOTE: +1 to length due to spacebar
+            word = self.next_word().unwrap();
+            chars += word.chars().count() + 1;
+        }
+    }
+
+    fn redo(&mut self) {
+        self.rng = SmallRng::seed_from_u64(self.rng_seed);
+    }
+
+    fn next_test(&mut self) {
+        self.rng_seed = rand::thread_rng().gen::<u64>();
+        self.rng = SmallRng::seed_from_u64(self.rng_seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    fn fill_words(generator: &mut LetterSetWordGenerator) -> Vec<String> {
+        let mut line = Vec::new();
+        generator.fill_line(&mut line, 80);
+        line.into_iter().map(|w| w.word).collect()
+    }
+
+    #[test]
+    fn pseudo_words_alternate_vowels_and_consonants() {
+        let options = LetterSetOptions {
+            min_length: 3,
+            max_length: 5,
+            real_words: false,
+        };
+        let mut generator = LetterSetWordGenerator::new("FJ ae".chars(), options);
+
+        let words = fill_words(&mut generator);
+
+        assert!(!words.is_empty());
+        for word in words {
+            let length = word.chars().count();
+            assert!((3..=5).contains(&length), "{}", word);
+
+            let chars: Vec<_> = word.chars().collect();
+            assert!(chars.iter().all(|c| "fjae".contains(*c)), "{}", word);
+            assert!(
+                chars
+                    .windows(2)
+                    .all(|pair| VOWELS.contains(&pair[0]) != VOWELS.contains(&pair[1])),
+                "{}",
+                word
+            );
+        }
+    }
+
+    #[rstest(real_words, case(true), case(false))]
+    fn real_words_are_filtered_to_letters_and_length(real_words: bool) {
+        let word_pool = vec![
+            String::from("sad"),
+            String::from("salads"),
+            String::from("the"),
+        ];
+        let options = LetterSetOptions {
+            min_length: 2,
+            max_length: 4,
+            real_words,
+        };
+        let generator = LetterSetWordGenerator::with_word_pool("asdl".chars(), word_pool, options);
+
+        let expected: Vec<String> = if real_words {
+            vec![String::from("sad")]
+        } else {
+            Vec::new()
+        };
+        assert_eq!(expected, generator.real_words);
+    }
+
+    #[test]
+    fn pseudo_words_are_mixed_in_with_many_real_words() {
+        // Pseudo-words never have two consonants in a row, so they can't match these words
+        let consonants = "sdfjkl";
+        let word_pool: Vec<String> = consonants
+            .chars()
+            .flat_map(|a| consonants.chars().map(move |b| format!("{}{}", a, b)))
+            .collect();
+        let options = LetterSetOptions {
+            min_length: 2,
+            max_length: 4,
+            real_words: true,
+        };
+        let mut generator =
+            LetterSetWordGenerator::with_word_pool("asdfjkl".chars(), word_pool.clone(), options);
+        assert!(generator.real_words.len() >= MIN_REAL_WORDS);
+
+        let words: Vec<String> = (0..10).flat_map(|_| fill_words(&mut generator)).collect();
+
+        assert!(words.iter().any(|word| word_pool.contains(word)));
+        assert!(words.iter().any(|word| !word_pool.contains(word)));
+    }
+
+    #[test]
+    fn words_are_reproducible_on_redo() {
+        let mut generator =
+            LetterSetWordGenerator::new("asdfjkl".chars(), LetterSetOptions::default());
+
+        let words = fill_words(&mut generator);
+        generator.redo();
+
+        assert_eq!(words, fill_words(&mut generator));
+    }
+
+    #[test]
+    fn empty_letter_set_leaves_line_empty() {
+        let mut generator = LetterSetWordGenerator::new(" ".chars(), LetterSetOptions::default());
+
+        assert!(fill_words(&mut generator).is_empty());
+    }
+}
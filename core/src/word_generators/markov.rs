@@ -0,0 +1,648 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Display,
+    fs, io,
+    path::Path,
+    sync::Arc,
+};
+
+use rand::{prelude::SmallRng, Rng, SeedableRng};
+
+use crate::word_pools::passages::ENGLISH_PASSAGES;
+
+use super::{fill_line_from_queue, DisplayedWord, WordGenerator};
+
+/// The number of previous letters used to pick the next letter, if none is specified.
+pub const DEFAULT_ORDER: usize = 2;
+
+/// The header at the start of every model file, followed by the order and corpus hash.
+const MODEL_HEADER: &str = "typetest-markov 1";
+
+/// The shortest word that is generated in words mode.
+const MIN_WORD_LENGTH: usize = 2;
+
+/// The longest word that is generated in words mode.
+const MAX_WORD_LENGTH: usize = 12;
+
+/// The number of attempts at generating a word within the length limits before settling for the
+/// last attempt.
+const MAX_WORD_ATTEMPTS: usize = 10;
+
+/// The longest sentence that is generated in sentences mode, in case the chain never ends.
+const MAX_SENTENCE_WORDS: usize = 40;
+
+/// Represents a Markov chain model trained on a corpus of text.
+///
+/// The model has two chains: one picks each letter of a word based on the previous letters, and
+/// the other picks each word of a sentence based on the previous word. Transitions are kept in a
+/// fixed order, so the same seed always produces the same text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarkovModel {
+    order: usize,
+    corpus_hash: u64,
+    letters: Chain,
+    words: Chain,
+}
+
+/// Represents what a Markov word generator produces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarkovMode {
+    /// Made-up words that follow the letter patterns in the corpus.
+    Words,
+
+    /// Sentences that follow the word patterns in the corpus.
+    Sentences,
+}
+
+/// Represents an error that occurred while loading a Markov model.
+#[derive(Debug)]
+pub enum MarkovError {
+    Io(io::Error),
+    InvalidHeader,
+    InvalidLine { line: usize, contents: String },
+}
+
+/// Implementation of an infinite word generator using a Markov chain model.
+pub struct MarkovWordGenerator {
+    model: Arc<MarkovModel>,
+    mode: MarkovMode,
+    pending: VecDeque<DisplayedWord>,
+    rng: SmallRng,
+    rng_seed: u64,
+}
+
+/// Represents the transitions from each context to the tokens that followed it, with counts.
+///
+/// An empty context is the start of a word or sentence, and a `None` token is the end of one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Chain {
+    transitions: BTreeMap<String, Vec<(Option<String>, u32)>>,
+}
+
+impl MarkovModel {
+    /// Trains a model on a corpus, using `order` previous letters to pick the next letter.
+    ///
+    /// Letters are taken from each word in lowercase, ignoring anything that isn't alphabetic.
+    /// Sentences end at a word ending in `.`, `!` or `?`, or at the end of a line.
+    pub fn train(corpus: &str, order: usize) -> Self {
+        let order = order.max(1);
+        let mut letters = Chain::default();
+        let mut words = Chain::default();
+
+        for line in corpus.lines() {
+            let mut previous_word = String::new();
+
+            for word in line.split_whitespace() {
+                let letters_in_word: Vec<String> = word
+                    .chars()
+                    .filter(|c| c.is_alphabetic())
+                    .flat_map(char::to_lowercase)
+                    .map(String::from)
+                    .collect();
+
+                if !letters_in_word.is_empty() {
+                    for i in 0..=letters_in_word.len() {
+                        let context = letters_in_word[i.saturating_sub(order)..i].concat();
+                        letters.add(context, letters_in_word.get(i).cloned());
+                    }
+                }
+
+                words.add(previous_word, Some(word.to_string()));
+                previous_word = word.to_string();
+
+                if ends_sentence(word) {
+                    words.add(previous_word, None);
+                    previous_word = String::new();
+                }
+            }
+
+            if !previous_word.is_empty() {
+                words.add(previous_word, None);
+            }
+        }
+
+        letters.sort();
+        words.sort();
+
+        Self {
+            order,
+            corpus_hash: hash_corpus(corpus),
+            letters,
+            words,
+        }
+    }
+
+    /// Loads a cached model if it was trained on the same corpus with the same order, or trains a
+    /// new one and caches it otherwise.
+    ///
+    /// Failing to write the cache doesn't prevent the model from being used; the model is just
+    /// trained again next time.
+    pub fn load_or_train(corpus: &str, order: usize, cache_path: impl AsRef<Path>) -> Self {
+        let cache_path = cache_path.as_ref();
+        if let Ok(model) = Self::load(cache_path) {
+            if model.is_trained_on(corpus, order) {
+                return model;
+            }
+        }
+
+        let model = Self::train(corpus, order);
+        let _ = model.save(cache_path);
+        model
+    }
+
+    /// Gets the number of previous letters used to pick the next letter.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Checks whether this model was trained on a corpus with a particular order.
+    pub fn is_trained_on(&self, corpus: &str, order: usize) -> bool {
+        self.order == order.max(1) && self.corpus_hash == hash_corpus(corpus)
+    }
+
+    /// Checks whether this model has no words to generate text from.
+    pub fn is_empty(&self) -> bool {
+        self.words.transitions.is_empty()
+    }
+
+    /// Generates a made-up word, or `None` if the model is empty.
+    fn generate_word(&self, rng: &mut impl Rng) -> Option<String> {
+        if self.letters.transitions.is_empty() {
+            return None;
+        }
+
+        let mut word = String::new();
+        for _ in 0..MAX_WORD_ATTEMPTS {
+            word.clear();
+
+            let mut letters: Vec<&str> = Vec::new();
+            while letters.len() < MAX_WORD_LENGTH {
+                let context = letters[letters.len().saturating_sub(self.order)..].concat();
+                match self.letters.sample(&context, rng) {
+                    Some(Some(letter)) => letters.push(letter),
+                    _ => break,
+                }
+            }
+
+            word.extend(letters);
+            if word.chars().count() >= MIN_WORD_LENGTH {
+                break;
+            }
+        }
+
+        Some(word)
+    }
+
+    /// Generates the words of a sentence, which is empty if the model is empty.
+    fn generate_sentence(&self, rng: &mut impl Rng) -> Vec<String> {
+        let mut sentence = Vec::new();
+        let mut previous_word = "";
+
+        while sentence.len() < MAX_SENTENCE_WORDS {
+            match self.words.sample(previous_word, rng) {
+                Some(Some(word)) => {
+                    sentence.push(word.to_string());
+                    previous_word = word;
+                }
+                _ => break,
+            }
+        }
+
+        sentence
+    }
+
+    /// Converts this model into the text of a model file.
+    ///
+    /// After the header, each context is written on a line starting with `l` for the letters chain
+    /// or `w` for the words chain, followed by pairs of the next token and its count. `^` marks an
+    /// empty context and `$` marks the end of a word or sentence.
+    pub fn encode(&self) -> String {
+        let mut text = format!(
+            "{} {} {:016x}\n",
+            MODEL_HEADER, self.order, self.corpus_hash
+        );
+        self.letters.encode_into('l', &mut text);
+        self.words.encode_into('w', &mut text);
+        text
+    }
+
+    /// Parses the text of a model file.
+    ///
+    /// The order must be at least 1, as it is for trained models. Every count must be positive, and
+    /// the counts for each context must add up to a number that can be sampled from.
+    pub fn parse(text: &str) -> Result<Self, MarkovError> {
+        let mut lines = text.lines();
+        let header = lines.next().unwrap_or_default();
+        let mut fields = header
+            .strip_prefix(MODEL_HEADER)
+            .ok_or(MarkovError::InvalidHeader)?
+            .split_whitespace();
+
+        let order = fields
+            .next()
+            .and_then(|o| o.parse().ok())
+            .filter(|&o| o > 0)
+            .ok_or(MarkovError::InvalidHeader)?;
+        let corpus_hash = fields
+            .next()
+            .and_then(|h| u64::from_str_radix(h, 16).ok())
+            .ok_or(MarkovError::InvalidHeader)?;
+
+        let mut letters = Chain::default();
+        let mut words = Chain::default();
+
+        for (i, line) in lines.enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let invalid = || MarkovError::InvalidLine {
+                line: i + 2,
+                contents: line.to_string(),
+            };
+
+            let mut fields = line.split(' ');
+            let chain = match fields.next() {
+                Some("l") => &mut letters,
+                Some("w") => &mut words,
+                _ => return Err(invalid()),
+            };
+
+            let context = fields.next().ok_or_else(invalid)?;
+            let context = decode_token(context)
+                .ok_or_else(invalid)?
+                .ok_or_else(invalid)?;
+
+            let mut next = Vec::new();
+            let mut total: u32 = 0;
+            while let Some(token) = fields.next() {
+                let token = decode_token(token).ok_or_else(invalid)?;
+                let count = fields
+                    .next()
+                    .and_then(|c| c.parse().ok())
+                    .filter(|&c| c > 0)
+                    .ok_or_else(invalid)?;
+
+                total = total.checked_add(count).ok_or_else(invalid)?;
+                next.push((token, count));
+            }
+
+            if next.is_empty() {
+                return Err(invalid());
+            }
+
+            chain.transitions.insert(context, next);
+        }
+
+        Ok(Self {
+            order,
+            corpus_hash,
+            letters,
+            words,
+        })
+    }
+
+    /// Loads a model file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MarkovError> {
+        let text = fs::read_to_string(path).map_err(MarkovError::Io)?;
+        Self::parse(&text)
+    }
+
+    /// Saves this model to a file, creating its directory if needed.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, self.encode())
+    }
+}
+
+impl Default for MarkovModel {
+    /// Trains a model on the built-in English passages.
+    fn default() -> Self {
+        Self::train(&ENGLISH_PASSAGES.join("\n\n"), DEFAULT_ORDER)
+    }
+}
+
+impl MarkovMode {
+    /// An array of all the available Markov modes.
+    pub const ALL_MODES: [MarkovMode; 2] = [MarkovMode::Words, MarkovMode::Sentences];
+}
+
+impl Default for MarkovMode {
+    fn default() -> Self {
+        MarkovMode::Words
+    }
+}
+
+impl Display for MarkovMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            MarkovMode::Words => f.write_str("Words"),
+            MarkovMode::Sentences => f.write_str("Sentences"),
+        }
+    }
+}
+
+impl Display for MarkovError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MarkovError::Io(e) => write!(f, "{}", e),
+            MarkovError::InvalidHeader => f.write_str("File is not a TypeTest Markov model"),
+            MarkovError::InvalidLine { line, contents } => {
+                write!(f, "Line {}: invalid entry '{}'", line, contents)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MarkovError {}
+
+impl MarkovWordGenerator {
+    pub fn new(model: Arc<MarkovModel>, mode: MarkovMode) -> Self {
+        let rng_seed = rand::thread_rng().gen::<u64>();
+        let rng = SmallRng::seed_from_u64(rng_seed);
+
+        Self {
+            model,
+            mode,
+            pending: VecDeque::new(),
+            rng,
+            rng_seed,
+        }
+    }
+
+    /// Queues up more words, returning `false` if the model couldn't generate any.
+    fn queue_words(&mut self) -> bool {
+        match self.mode {
+            MarkovMode::Words => match self.model.generate_word(&mut self.rng) {
+                Some(word) => self.pending.push_back(DisplayedWord::new(word)),
+                None => return false,
+            },
+            MarkovMode::Sentences => {
+                let sentence = self.model.generate_sentence(&mut self.rng);
+                if sentence.is_empty() {
+                    return false;
+                }
+
+                self.pending
+                    .extend(sentence.into_iter().map(DisplayedWord::new));
+            }
+        }
+
+        true
+    }
+}
+
+impl WordGenerator for MarkovWordGenerator {
+    fn fill_line(&mut self, line: &mut Vec<DisplayedWord>, max_chars: usize) {
+        line.clear();
+
+        while !fill_line_from_queue(&mut self.pending, line, max_chars) {
+            if !self.queue_words() {
+                return;
+            }
+        }
+    }
+
+    fn redo(&mut self) {
+        self.rng = SmallRng::seed_from_u64(self.rng_seed);
+        self.pending.clear();
+    }
+
+    fn next_test(&mut self) {
+        self.rng_seed = rand::thread_rng().gen::<u64>();
+        self.rng = SmallRng::seed_from_u64(self.rng_seed);
+        self.pending.clear();
+    }
+}
+
+impl Chain {
+    /// Records that a token followed a context.
+    fn add(&mut self, context: String, token: Option<String>) {
+        let next = self.transitions.entry(context).or_default();
+        match next.iter_mut().find(|(t, _)| *t == token) {
+            Some((_, count)) => *count += 1,
+            None => next.push((token, 1)),
+        }
+    }
+
+    /// Sorts the tokens for each context, so that sampling doesn't depend on the training order.
+    fn sort(&mut self) {
+        for next in self.transitions.values_mut() {
+            next.sort();
+        }
+    }
+
+    /// Picks a token that followed a context, weighted by how often it did.
+    ///
+    /// Returns `None` if the context never appeared, or if its counts can't be sampled from.
+    fn sample(&self, context: &str, rng: &mut impl Rng) -> Option<Option<&str>> {
+        let next = self.transitions.get(context)?;
+        let total = next
+            .iter()
+            .try_fold(0u32, |total, (_, count)| total.checked_add(*count))
+            .filter(|&total| total > 0)?;
+
+        let mut target = rng.gen_range(0..total);
+        for (token, count) in next {
+            if target < *count {
+                return Some(token.as_deref());
+            }
+
+            target -= count;
+        }
+
+        None
+    }
+
+    /// Writes each context in this chain as a line starting with `prefix`.
+    fn encode_into(&self, prefix: char, text: &mut String) {
+        for (context, next) in &self.transitions {
+            text.push(prefix);
+            text.push(' ');
+            text.push_str(&encode_token(Some(context)));
+
+            for (token, count) in next {
+                text.push_str(&format!(" {} {}", encode_token(token.as_ref()), count));
+            }
+
+            text.push('\n');
+        }
+    }
+}
+
+/// Checks whether a word ends a sentence, ignoring any closing quotes or brackets.
+fn ends_sentence(word: &str) -> bool {
+    word.trim_end_matches(|c| matches!(c, '"' | '\'' | ')' | ']'))
+        .ends_with(|c| matches!(c, '.' | '!' | '?'))
+}
+
+/// Encodes a token for a model file, where `None` is written as `$`.
+///
+/// Tokens that would be mistaken for a marker are escaped with a leading `\`. Tokens never contain
+/// whitespace, since the corpus is split at whitespace.
+fn encode_token(token: Option<&String>) -> String {
+    match token {
+        None => String::from("$"),
+        Some(token) if token.is_empty() => String::from("^"),
+        Some(token) if token == "^" || token == "$" || token.starts_with('\\') => {
+            format!("\\{}", token)
+        }
+        Some(token) => token.clone(),
+    }
+}
+
+/// Reverses [`encode_token`], where `^` is decoded as an empty token.
+///
+/// Returns `None` if the token is empty.
+fn decode_token(token: &str) -> Option<Option<String>> {
+    match token {
+        "" => None,
+        "$" => Some(None),
+        "^" => Some(Some(String::new())),
+        _ => Some(Some(token.strip_prefix('\\').unwrap_or(token).to_string())),
+    }
+}
+
+/// Hashes a corpus using 64-bit FNV-1a, which is stable between runs and platforms.
+fn hash_corpus(corpus: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    corpus.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    const CORPUS: &str = "The cat sat on the mat. The cat ran!\n\nA dog barked at the cat";
+
+    fn fill_words(generator: &mut MarkovWordGenerator) -> Vec<String> {
+        let mut line = Vec::new();
+        generator.fill_line(&mut line, 80);
+        line.into_iter().map(|w| w.word).collect()
+    }
+
+    #[test]
+    fn train_counts_letters_and_words() {
+        let model = MarkovModel::train(CORPUS, 1);
+
+        let after_t = &model.letters.transitions["t"];
+        assert!(after_t.contains(&(Some(String::from("h")), 4)));
+        assert!(after_t.contains(&(None, 6)));
+
+        let starts = &model.words.transitions[""];
+        assert_eq!(
+            &vec![(Some(String::from("A")), 1), (Some(String::from("The")), 2)],
+            starts
+        );
+
+        assert!(model.words.transitions["mat."].contains(&(None, 1)));
+        assert!(model.words.transitions["cat"].contains(&(None, 1)));
+    }
+
+    #[rstest(order, case(1), case(2), case(3))]
+    fn encoded_model_round_trips(order: usize) {
+        let model = MarkovModel::train("^ $ \\back \"quoted\" words. More words!", order);
+
+        assert_eq!(model, MarkovModel::parse(&model.encode()).unwrap());
+    }
+
+    #[rstest(
+        text,
+        case(""),
+        case("not a model"),
+        case("typetest-markov 1 x 0"),
+        case("typetest-markov 1 0 0"),
+        case("typetest-markov 1 2 0\nx a b 1"),
+        case("typetest-markov 1 2 0\nl a b"),
+        case("typetest-markov 1 2 0\nl a"),
+        case("typetest-markov 1 2 0\nl a b 4294967295 c 1")
+    )]
+    fn invalid_models_are_rejected(text: &str) {
+        assert!(MarkovModel::parse(text).is_err());
+    }
+
+    #[test]
+    fn zero_counts_are_rejected() {
+        let result = MarkovModel::parse("typetest-markov 1 2 0\nl a b 1\nl b c 0 $ 0");
+
+        assert!(matches!(
+            result,
+            Err(MarkovError::InvalidLine { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn is_trained_on_matches_corpus_and_order() {
+        let model = MarkovModel::train(CORPUS, 2);
+
+        assert!(model.is_trained_on(CORPUS, 2));
+        assert!(!model.is_trained_on(CORPUS, 3));
+        assert!(!model.is_trained_on("Another corpus.", 2));
+    }
+
+    #[rstest(mode, case(MarkovMode::Words), case(MarkovMode::Sentences))]
+    fn words_are_reproducible_on_redo(mode: MarkovMode) {
+        let mut generator = MarkovWordGenerator::new(Arc::new(MarkovModel::default()), mode);
+
+        let words = fill_words(&mut generator);
+        generator.redo();
+
+        assert!(!words.is_empty());
+        assert_eq!(words, fill_words(&mut generator));
+    }
+
+    #[test]
+    fn words_only_use_letters_from_corpus() {
+        let model = MarkovModel::train(CORPUS, DEFAULT_ORDER);
+        let mut generator = MarkovWordGenerator::new(Arc::new(model), MarkovMode::Words);
+
+        for word in fill_words(&mut generator) {
+            let length = word.chars().count();
+            assert!(length <= MAX_WORD_LENGTH, "{}", word);
+            assert!(
+                word.chars().all(|c| "thecatsonmrandogbk".contains(c)),
+                "{}",
+                word
+            );
+        }
+    }
+
+    #[test]
+    fn sentences_follow_corpus_words() {
+        let model = MarkovModel::train(CORPUS, DEFAULT_ORDER);
+        let mut generator =
+            MarkovWordGenerator::new(Arc::new(model.clone()), MarkovMode::Sentences);
+
+        let words = fill_words(&mut generator);
+        for pair in words.windows(2) {
+            let follows = model.words.transitions[&pair[0]]
+                .iter()
+                .any(|(token, _)| token.as_deref() == Some(pair[1].as_str()));
+            let starts = model.words.transitions[""]
+                .iter()
+                .any(|(token, _)| token.as_deref() == Some(pair[1].as_str()));
+
+            assert!(follows || starts, "{} {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn empty_model_leaves_line_empty() {
+        let model = MarkovModel::train("", DEFAULT_ORDER);
+
+        for mode in MarkovMode::ALL_MODES.iter() {
+            let mut generator = MarkovWordGenerator::new(Arc::new(model.clone()), *mode);
+            assert!(fill_words(&mut generator).is_empty());
+        }
+    }
+}
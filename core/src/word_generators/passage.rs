@@ -0,0 +1,72 @@
+use std::collections::VecDeque;
+
+use rand::{
+    prelude::{SliceRandom, SmallRng},
+    Rng, SeedableRng,
+};
+
+use crate::word_pools::passages::ENGLISH_PASSAGES;
+
+use super::{fill_line_from_queue, tokenize_lines, DisplayedWord, WordGenerator};
+
+/// Implementation of an infinite word generator that replicates random passages of text.
+///
+/// Each line in a passage is treated as a paragraph, which is wrapped to fit the display and ends
+/// with a newline.
+pub struct PassageWordGenerator {
+    passages: Vec<String>,
+    pending: VecDeque<DisplayedWord>,
+    rng: SmallRng,
+    rng_seed: u64,
+}
+
+impl PassageWordGenerator {
+    pub fn new(passages: Vec<String>) -> Self {
+        let rng_seed = rand::thread_rng().gen::<u64>();
+        let rng = SmallRng::seed_from_u64(rng_seed);
+
+        Self {
+            passages,
+            pending: VecDeque::new(),
+            rng,
+            rng_seed,
+        }
+    }
+
+    /// Queues up the words for another random passage.
+    fn queue_passage(&mut self) {
+        let passage = self
+            .passages
+            .choose(&mut self.rng)
+            .expect("Passage pool is empty!");
+
+        self.pending.extend(tokenize_lines(passage, false));
+    }
+}
+
+impl Default for PassageWordGenerator {
+    fn default() -> Self {
+        Self::new(ENGLISH_PASSAGES.iter().map(|s| s.to_string()).collect())
+    }
+}
+
+impl WordGenerator for PassageWordGenerator {
+    fn fill_line(&mut self, line: &mut Vec<DisplayedWord>, max_chars: usize) {
+        line.clear();
+
+        while !fill_line_from_queue(&mut self.pending, line, max_chars) {
+            self.queue_passage();
+        }
+    }
+
+    fn redo(&mut self) {
+        self.rng = SmallRng::seed_from_u64(self.rng_seed);
+        self.pending.clear();
+    }
+
+    fn next_test(&mut self) {
+        self.rng_seed = rand::thread_rng().gen::<u64>();
+        self.rng = SmallRng::seed_from_u64(self.rng_seed);
+        self.pending.clear();
+    }
+}
@@ -1,35 +1,133 @@
-use rand::{
-    prelude::{SliceRandom, SmallRng},
-    Rng, SeedableRng,
-};
+use std::collections::VecDeque;
+
+use rand::{prelude::SmallRng, Rng, SeedableRng};
 
 use crate::word_pools::default_english::DEFAULT_ENGLISH;
 
 use super::{DisplayedWord, WordGenerator};
 
+/// Represents the options for picking random words from a pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RandomWordOptions {
+    /// The minimum number of letters in each word.
+    pub min_length: usize,
+
+    /// The maximum number of letters in each word, or `None` for no limit.
+    pub max_length: Option<usize>,
+
+    /// The number of previous words that each new word must be different from.
+    ///
+    /// This is reduced if the pool doesn't have enough words to avoid them all.
+    pub repeat_window: usize,
+
+    /// Words that are never picked, ignoring case.
+    pub blocklist: Vec<String>,
+}
+
 /// Implementation of an infinite word generator using random words from a pool.
 pub struct InfiniteWordGenerator {
     word_pool: Vec<String>,
+    repeat_window: usize,
+    /// The indices of the most recently picked words, from oldest to newest.
+    recent: VecDeque<usize>,
+    /// The word that didn't fit on the previous line, which starts the next line.
+    pending: Option<String>,
     rng: SmallRng,
     rng_seed: u64,
 }
 
+impl Default for RandomWordOptions {
+    fn default() -> Self {
+        Self {
+            min_length: 1,
+            max_length: None,
+            repeat_window: 1,
+            blocklist: Vec::new(),
+        }
+    }
+}
+
 impl InfiniteWordGenerator {
     pub fn new(word_pool: Vec<String>) -> Self {
+        Self::with_options(word_pool, RandomWordOptions::default())
+    }
+
+    /// Creates a new generator for a word pool, only keeping the words allowed by the options.
+    pub fn with_options(word_pool: Vec<String>, options: RandomWordOptions) -> Self {
         let rng_seed = rand::thread_rng().gen::<u64>();
         let rng = SmallRng::seed_from_u64(rng_seed);
 
+        let blocklist: Vec<String> = options
+            .blocklist
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect();
+
+        let word_pool: Vec<String> = word_pool
+            .into_iter()
+            .filter(|word| {
+                let length = word.chars().count();
+                length >= options.min_length
+                    && options.max_length.map_or(true, |max| length <= max)
+                    && !blocklist.contains(&word.to_lowercase())
+            })
+            .collect();
+
+        let repeat_window = options.repeat_window.min(word_pool.len().saturating_sub(1));
+
         Self {
             word_pool,
+            repeat_window,
+            recent: VecDeque::with_capacity(repeat_window + 1),
+            pending: None,
             rng,
             rng_seed,
         }
     }
+
+    /// Picks a random word that isn't one of the recently picked words.
+    ///
+    /// Returns `None` if the pool is empty.
+    fn next_word(&mut self) -> Option<&str> {
+        if self.word_pool.is_empty() {
+            return None;
+        }
+
+        // Pick from the words that aren't recent, then skip over the recent words to find its index
+        let mut excluded: Vec<usize> = self.recent.iter().copied().collect();
+        excluded.sort_unstable();
+
+        let mut index = self.rng.gen_range(0..self.word_pool.len() - excluded.len());
+        for recent_index in excluded {
+            if recent_index <= index {
+                index += 1;
+            }
+        }
+
+        if self.repeat_window > 0 {
+            if self.recent.len() == self.repeat_window {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(index);
+        }
+
+        Some(&self.word_pool[index])
+    }
 }
 
 impl Default for InfiniteWordGenerator {
     fn default() -> Self {
-        Self::new(DEFAULT_ENGLISH.iter().map(|s| s.to_string()).collect())
+        Self::from(RandomWordOptions::default())
+    }
+}
+
+impl From<RandomWordOptions> for InfiniteWordGenerator {
+    /// Creates a new generator using the default English word pool.
+    fn from(options: RandomWordOptions) -> Self {
+        Self::with_options(
+            DEFAULT_ENGLISH.iter().map(|s| s.to_string()).collect(),
+            options,
+        )
     }
 }
 
@@ -37,31 +135,146 @@ impl WordGenerator for InfiniteWordGenerator {
     fn fill_line(&mut self, line: &mut Vec<super::DisplayedWord>, max_chars: usize) {
         line.clear();
 
-        let mut chars = 0;
-        let mut word = self
-            .word_pool
-            .choose(&mut self.rng)
-            .expect("Word pool is empty!");
-        chars += word.len();
+        let mut word = match self.pending.take() {
+            Some(word) => word,
+            None => match self.next_word() {
+                Some(word) => word.to_string(),
+                None => return,
+            },
+        };
+        let mut chars = word.len();
 
         while chars < max_chars {
             line.push(DisplayedWord::new(word));
 
             // NOTE: +1 to length due to spacebar
-            word = self
-                .word_pool
-                .choose(&mut self.rng)
-                .expect("Word pool is empty!");
+            word = self.next_word().unwrap().to_string();
             chars += word.len() + 1;
         }
+
+        self.pending = Some(word);
     }
 
     fn redo(&mut self) {
         self.rng = SmallRng::seed_from_u64(self.rng_seed);
+        self.recent.clear();
+        self.pending = None;
     }
 
     fn next_test(&mut self) {
         self.rng_seed = rand::thread_rng().gen::<u64>();
         self.rng = SmallRng::seed_from_u64(self.rng_seed);
+        self.recent.clear();
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    fn pool(words: &[&str]) -> Vec<String> {
+        words.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn fill_words(generator: &mut InfiniteWordGenerator) -> Vec<String> {
+        let mut words = Vec::new();
+        let mut line = Vec::new();
+        for _ in 0..10 {
+            generator.fill_line(&mut line, 80);
+            words.extend(line.drain(..).map(|w| w.word));
+        }
+
+        words
+    }
+
+    #[rstest(repeat_window, case(1), case(2), case(3))]
+    fn words_are_not_repeated_within_window(repeat_window: usize) {
+        let options = RandomWordOptions {
+            repeat_window,
+            ..RandomWordOptions::default()
+        };
+        let mut generator = InfiniteWordGenerator::with_options(
+            pool(&["alpha", "bravo", "charlie", "delta"]),
+            options,
+        );
+
+        let words = fill_words(&mut generator);
+        for window in words.windows(repeat_window + 1) {
+            for (i, word) in window.iter().enumerate() {
+                assert!(!window[i + 1..].contains(word), "{:?}", window);
+            }
+        }
+    }
+
+    #[test]
+    fn repeat_window_is_limited_by_pool_size() {
+        let options = RandomWordOptions {
+            repeat_window: 10,
+            ..RandomWordOptions::default()
+        };
+        let mut generator = InfiniteWordGenerator::with_options(pool(&["ab", "cd"]), options);
+
+        let words = fill_words(&mut generator);
+        assert!(words.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[rstest(
+        min_length,
+        max_length,
+        expected,
+        case(1, None, vec!["a", "to", "the", "them"]),
+        case(2, Some(3), vec!["to", "the"]),
+        case(4, None, vec!["them"])
+    )]
+    fn words_are_filtered_by_length(
+        min_length: usize,
+        max_length: Option<usize>,
+        expected: Vec<&str>,
+    ) {
+        let options = RandomWordOptions {
+            min_length,
+            max_length,
+            ..RandomWordOptions::default()
+        };
+        let generator =
+            InfiniteWordGenerator::with_options(pool(&["a", "to", "the", "them"]), options);
+
+        assert_eq!(pool(&expected), generator.word_pool);
+    }
+
+    #[test]
+    fn blocklist_ignores_case() {
+        let options = RandomWordOptions {
+            blocklist: pool(&["THE", "to"]),
+            ..RandomWordOptions::default()
+        };
+        let generator =
+            InfiniteWordGenerator::with_options(pool(&["a", "To", "the", "them"]), options);
+
+        assert_eq!(pool(&["a", "them"]), generator.word_pool);
+    }
+
+    #[test]
+    fn words_are_reproducible_on_redo() {
+        let mut generator = InfiniteWordGenerator::default();
+
+        let words = fill_words(&mut generator);
+        generator.redo();
+
+        assert_eq!(words, fill_words(&mut generator));
+    }
+
+    #[test]
+    fn empty_pool_leaves_line_empty() {
+        let options = RandomWordOptions {
+            min_length: 50,
+            ..RandomWordOptions::default()
+        };
+        let mut generator = InfiniteWordGenerator::from(options);
+
+        assert!(fill_words(&mut generator).is_empty());
     }
 }
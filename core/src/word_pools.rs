@@ -1 +1,3 @@
+pub mod code_snippets;
 pub mod default_english;
+pub mod passages;
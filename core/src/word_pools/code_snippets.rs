@@ -0,0 +1,111 @@
+//! Code snippets for the code word generator.
+//!
+//! Each snippet is indented using 4 spaces per level, which the generator converts into tabs.
+
+/// Rust code snippets.
+pub const RUST: [&str; 6] = [
+    r#"fn main() {
+    let name = "world";
+    println!("Hello, {}!", name);
+}"#,
+    r#"pub fn factorial(n: u64) -> u64 {
+    match n {
+        0 | 1 => 1,
+        _ => n * factorial(n - 1),
+    }
+}"#,
+    r#"let evens: Vec<u32> = (1..=20)
+    .filter(|x| x % 2 == 0)
+    .collect();"#,
+    r#"#[derive(Clone, Debug)]
+struct Point {
+    x: f32,
+    y: f32,
+}"#,
+    r#"impl Point {
+    fn length(&self) -> f32 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+}"#,
+    r#"for (i, line) in text.lines().enumerate() {
+    if line.is_empty() {
+        continue;
+    }
+    println!("{}: {}", i, line);
+}"#,
+];
+
+/// Python code snippets.
+pub const PYTHON: [&str; 6] = [
+    r#"def greet(name):
+    return f"Hello, {name}!""#,
+    r#"def fib(n):
+    a, b = 0, 1
+    for _ in range(n):
+        a, b = b, a + b
+    return a"#,
+    r#"squares = [x ** 2 for x in range(10) if x % 2 == 0]"#,
+    r#"with open("data.txt") as f:
+    for line in f:
+        print(line.strip())"#,
+    r#"class Stack:
+    def __init__(self):
+        self.items = []
+
+    def push(self, item):
+        self.items.append(item)"#,
+    r#"try:
+    value = int(text)
+except ValueError:
+    value = 0"#,
+];
+
+/// JavaScript code snippets.
+pub const JAVASCRIPT: [&str; 6] = [
+    r#"const add = (a, b) => a + b;"#,
+    r#"function sum(values) {
+    return values.reduce((acc, x) => acc + x, 0);
+}"#,
+    r#"const user = { name: "Ada", age: 36 };
+const { name, ...rest } = user;"#,
+    r#"for (let i = 0; i < items.length; i++) {
+    if (items[i] === target) {
+        return i;
+    }
+}"#,
+    r#"async function load(url) {
+    const response = await fetch(url);
+    return response.json();
+}"#,
+    r#"button.addEventListener("click", (event) => {
+    event.preventDefault();
+    count += 1;
+});"#,
+];
+
+/// C code snippets.
+pub const C: [&str; 6] = [
+    r#"#include <stdio.h>
+
+int main(void) {
+    printf("Hello, world!\n");
+    return 0;
+}"#,
+    r#"int max(int a, int b) {
+    return a > b ? a : b;
+}"#,
+    r#"for (size_t i = 0; i < len; ++i) {
+    sum += values[i];
+}"#,
+    r#"struct node {
+    int value;
+    struct node *next;
+};"#,
+    r#"char *buffer = malloc(size);
+if (buffer == NULL) {
+    return -1;
+}"#,
+    r#"while (*src != '\0') {
+    *dst++ = *src++;
+}"#,
+];
@@ -0,0 +1,19 @@
+//! Passages of text for the passage word generator.
+//!
+//! Each paragraph is written on a single line, with paragraphs separated by blank lines.
+
+/// Public domain English passages.
+pub const ENGLISH_PASSAGES: [&str; 4] = [
+    "It is a truth universally acknowledged, that a single man in possession of a good fortune, must be in want of a wife.
+
+However little known the feelings or views of such a man may be on his first entering a neighbourhood, this truth is so well fixed in the minds of the surrounding families, that he is considered the rightful property of some one or other of their daughters.",
+    "Alice was beginning to get very tired of sitting by her sister on the bank, and of having nothing to do: once or twice she had peeped into the book her sister was reading, but it had no pictures or conversations in it.
+
+\"And what is the use of a book,\" thought Alice, \"without pictures or conversations?\"",
+    "Call me Ishmael. Some years ago, never mind how long precisely, having little or no money in my purse, and nothing particular to interest me on shore, I thought I would sail about a little and see the watery part of the world.
+
+It is a way I have of driving off the spleen and regulating the circulation.",
+    "You will rejoice to hear that no disaster has accompanied the commencement of an enterprise which you have regarded with such evil forebodings.
+
+I arrived here yesterday, and my first task is to assure my dear sister of my welfare and increasing confidence in the success of my undertaking.",
+];
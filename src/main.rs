@@ -1,14 +1,24 @@
 #![cfg_attr(all(windows, not(debug_assertions)), windows_subsystem = "windows")]
 
+use std::sync::Arc;
+
 use iced::{Align, Application, Column, Container, Length, Text};
+use typetest_core::{
+    history::History,
+    layouts::{BuiltinLayout, KeyboardLayout, LayoutEmulation},
+};
 use typetest_themes::{ApplicationTheme, Theme};
 use views::{
+    history::{HistoryMessage, HistoryState},
+    lessons::{LessonsMessage, LessonsState},
+    replay::{ReplayMessage, ReplayState},
     results::{ResultsMessage, ResultsState},
     settings::{SettingsMessage, SettingsState},
     typing_test::{TypingTestMessage, TypingTestState},
     Action, View,
 };
 
+mod storage;
 mod views;
 mod widgets;
 
@@ -16,10 +26,16 @@ mod widgets;
 pub struct App {
     current_view: View,
     current_theme: Box<dyn ApplicationTheme>,
+    history: History,
+    keyboard_layouts: Vec<KeyboardLayout>,
+    keyboard_layout: KeyboardLayout,
 
     typing_test_state: TypingTestState,
     results_state: ResultsState,
     settings_state: SettingsState,
+    history_state: HistoryState,
+    replay_state: ReplayState,
+    lessons_state: LessonsState,
 }
 
 /// Top-level message for the application.
@@ -29,6 +45,9 @@ pub enum AppMessage {
     TypingTest(TypingTestMessage),
     Results(ResultsMessage),
     Settings(SettingsMessage),
+    History(HistoryMessage),
+    Replay(ReplayMessage),
+    Lessons(LessonsMessage),
 }
 
 fn main() -> Result<(), iced::Error> {
@@ -41,15 +60,43 @@ impl Application for App {
     type Flags = ();
 
     fn new(_flags: Self::Flags) -> (Self, iced::Command<Self::Message>) {
-        let app = App {
+        let keyboard_layouts = storage::load_keyboard_layouts();
+        let layout_names = keyboard_layouts
+            .iter()
+            .map(|layout| layout.name().to_string())
+            .collect();
+        let keyboard_layout = KeyboardLayout::default();
+        let lessons_state = LessonsState::new(storage::load_curricula(), &keyboard_layout);
+
+        let mut app = App {
             current_view: View::TypingTest,
             current_theme: Theme::DefaultDark.into(),
+            history: storage::load_history(),
+            keyboard_layouts,
+            keyboard_layout,
 
             typing_test_state: TypingTestState::new(),
             results_state: ResultsState::new(),
-            settings_state: SettingsState::new(),
+            settings_state: SettingsState::new(layout_names),
+            history_state: HistoryState::new(),
+            replay_state: ReplayState::new(),
+            lessons_state,
         };
 
+        app.history_state.update_history(&app.history);
+
+        // Reuse the most recent custom text from previous runs
+        if let Some(text) = app.settings_state.recent_custom_text() {
+            let text = text.to_string();
+            app.typing_test_state.update_custom_text(text);
+        }
+
+        // Reuse the Markov model trained on the most recent corpus from previous runs
+        if let Some(model) = storage::load_markov_model() {
+            app.typing_test_state.update_markov_model(Arc::new(model));
+            app.settings_state.use_cached_markov_model();
+        }
+
         (app, iced::Command::none())
     }
 
@@ -60,12 +107,12 @@ impl Application for App {
     fn update(
         &mut self,
         message: Self::Message,
-        _clipboard: &mut iced::Clipboard,
+        clipboard: &mut iced::Clipboard,
     ) -> iced::Command<Self::Message> {
         match message {
             AppMessage::TypingTest(message) => {
                 if let TypingTestMessage::Action(action) = &message {
-                    self.handle_action(action);
+                    self.handle_action(action, clipboard);
                 }
 
                 self.typing_test_state
@@ -74,20 +121,41 @@ impl Application for App {
             }
             AppMessage::Results(message) => {
                 if let ResultsMessage::Action(action) = &message {
-                    self.handle_action(action);
+                    self.handle_action(action, clipboard);
                 }
 
                 self.results_state.update(message).map(AppMessage::Results)
             }
             AppMessage::Settings(message) => {
                 if let SettingsMessage::Action(action) = &message {
-                    self.handle_action(action);
+                    self.handle_action(action, clipboard);
                 }
 
                 self.settings_state
                     .update(message)
                     .map(AppMessage::Settings)
             }
+            AppMessage::History(message) => {
+                if let HistoryMessage::Action(action) = &message {
+                    self.handle_action(action, clipboard);
+                }
+
+                self.history_state.update(message).map(AppMessage::History)
+            }
+            AppMessage::Replay(message) => {
+                if let ReplayMessage::Action(action) = &message {
+                    self.handle_action(action, clipboard);
+                }
+
+                self.replay_state.update(message).map(AppMessage::Replay)
+            }
+            AppMessage::Lessons(message) => {
+                if let LessonsMessage::Action(action) = &message {
+                    self.handle_action(action, clipboard);
+                }
+
+                self.lessons_state.update(message).map(AppMessage::Lessons)
+            }
         }
     }
 
@@ -106,6 +174,18 @@ impl Application for App {
                 .settings_state
                 .view(&self.current_theme)
                 .map(AppMessage::Settings),
+            View::History => self
+                .history_state
+                .view(&self.current_theme)
+                .map(AppMessage::History),
+            View::Replay => self
+                .replay_state
+                .view(&self.current_theme)
+                .map(AppMessage::Replay),
+            View::Lessons => self
+                .lessons_state
+                .view(&self.current_theme)
+                .map(AppMessage::Lessons),
         };
 
         let inner_container = Container::new(inner_view)
@@ -130,29 +210,120 @@ impl Application for App {
     }
 
     fn subscription(&self) -> iced::Subscription<Self::Message> {
-        if let View::TypingTest = self.current_view {
-            self.typing_test_state
+        match self.current_view {
+            View::TypingTest => self
+                .typing_test_state
                 .subscription()
-                .map(AppMessage::TypingTest)
-        } else {
-            iced::Subscription::none()
+                .map(AppMessage::TypingTest),
+            View::Replay => self.replay_state.subscription().map(AppMessage::Replay),
+            _ => iced::Subscription::none(),
         }
     }
 }
 
 impl App {
     /// Handles any application-wide actions signalled by the views.
-    fn handle_action(&mut self, action: &Action) {
+    fn handle_action(&mut self, action: &Action, clipboard: &mut iced::Clipboard) {
         match action {
             Action::ChangeTheme(theme) => self.current_theme = (*theme).into(),
             Action::ChangeView(view) => self.current_view = *view,
+            Action::ChangeKeyboardLayout(name) => {
+                if let Some(layout) = self.find_keyboard_layout(name).cloned() {
+                    self.keyboard_layout = layout.clone();
+                    self.lessons_state.update_keyboard_layout(&layout);
+                    self.results_state.update_keyboard_layout(layout);
+                }
+            }
+            Action::ChangeEmulatedLayout(name) => {
+                // Emulated layouts are typed using the physical key positions on a QWERTY keyboard
+                let emulation = name
+                    .as_ref()
+                    .and_then(|name| self.find_keyboard_layout(name))
+                    .map(|layout| LayoutEmulation::new(&BuiltinLayout::Qwerty.layout(), layout));
+
+                self.typing_test_state.update_layout_emulation(emulation);
+            }
             Action::ChangeTimeLength(time) => self.typing_test_state.update_time_length(*time),
+            Action::ChangeGeneratorKind(kind) => {
+                self.typing_test_state.update_generator_kind(*kind)
+            }
+            Action::ChangeRandomWordOptions(options) => self
+                .typing_test_state
+                .update_random_word_options(options.clone()),
+            Action::ChangeCodeLanguage(language) => {
+                self.typing_test_state.update_code_language(*language)
+            }
+            Action::ChangeCustomText(text) => {
+                self.typing_test_state.update_custom_text(text.clone())
+            }
+            Action::ChangeCustomTextOptions(options) => {
+                self.typing_test_state.update_custom_text_options(*options)
+            }
+            Action::ChangeLetterSet(letters) => {
+                self.typing_test_state.update_letter_set(letters.clone())
+            }
+            Action::ChangeLetterSetOptions(options) => {
+                self.typing_test_state.update_letter_set_options(*options)
+            }
+            Action::ChangeMarkovModel(model) => {
+                self.typing_test_state.update_markov_model(model.clone())
+            }
+            Action::ChangeMarkovMode(mode) => self.typing_test_state.update_markov_mode(*mode),
+            Action::PasteCustomText => {
+                if let Some(text) = clipboard.read() {
+                    self.settings_state.paste_custom_text(text);
+                }
+            }
+            Action::ChangeStopOnError(mode) => self.typing_test_state.update_stop_on_error(*mode),
+            Action::ChangeConfidenceMode(mode) => {
+                self.typing_test_state.update_confidence_mode(*mode)
+            }
+            Action::ChangeFailConditions(conditions) => {
+                self.typing_test_state.update_fail_conditions(*conditions)
+            }
+            Action::ChangeScoring(scoring) => self.typing_test_state.update_scoring(*scoring),
+
+            Action::DisplayResults(result) => {
+                // Emulated tests are typed on the emulated layout, so fingers are assigned using it
+                let layout = result
+                    .emulated_layout
+                    .as_deref()
+                    .and_then(|name| self.find_keyboard_layout(name))
+                    .unwrap_or(&self.keyboard_layout)
+                    .clone();
+
+                self.history.push(result.as_ref().clone());
+                self.history_state.update_history(&self.history);
+                self.save_history();
+                self.results_state
+                    .update_result(result.as_ref().clone(), &self.history, &layout);
 
-            Action::DisplayResults(stats) => {
-                self.results_state.update_stats(stats.clone());
+                let lesson_status = self.lessons_state.record_result(result);
+                self.results_state.update_lesson_status(lesson_status);
                 self.current_view = View::Results;
             }
 
+            Action::ImportResults(results) => {
+                self.history.import(results.iter().cloned());
+                self.history_state.update_history(&self.history);
+                self.save_history();
+            }
+
+            Action::ShowReplay(replay) => {
+                self.replay_state.open(replay.clone(), self.current_view);
+                self.current_view = View::Replay;
+            }
+            Action::PracticeMissedWords(words) => {
+                self.typing_test_state.start_practice(words);
+                self.current_view = View::TypingTest;
+            }
+            Action::StartLesson(id) => {
+                if let Some(lesson) = self.lessons_state.find_lesson(id) {
+                    self.typing_test_state.start_lesson(id.clone(), lesson);
+                    self.current_view = View::TypingTest;
+                }
+            }
+
             Action::RetryTest => {
                 self.typing_test_state.reset_test_state(false);
                 self.current_view = View::TypingTest;
@@ -164,4 +335,18 @@ impl App {
             }
         }
     }
+
+    /// Finds one of the loaded keyboard layouts by name.
+    fn find_keyboard_layout(&self, name: &str) -> Option<&KeyboardLayout> {
+        self.keyboard_layouts
+            .iter()
+            .find(|layout| layout.name() == name)
+    }
+
+    /// Saves the history so that it is loaded again on the next run.
+    fn save_history(&mut self) {
+        if let Err(e) = storage::save_history(&self.history) {
+            self.history_state.history_not_saved(e);
+        }
+    }
 }
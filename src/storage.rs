@@ -0,0 +1,218 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use typetest_core::{
+    history::{export, History, TestResult},
+    layouts::{BuiltinLayout, KeyboardLayout},
+    lessons::{Curriculum, LessonProgress},
+    word_generators::markov::{self, MarkovModel},
+};
+
+/// The maximum number of custom texts that are kept for reuse.
+pub const MAX_CUSTOM_TEXTS: usize = 5;
+
+/// Gets the directory where the application's data is stored, if one is available.
+fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("typetest"))
+}
+
+/// Gets the directory where recently used custom texts are stored.
+fn custom_texts_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("custom_texts"))
+}
+
+/// Gets the directory where custom keyboard layouts are stored.
+fn layouts_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("layouts"))
+}
+
+/// Gets the directory where custom lessons are stored.
+fn lessons_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("lessons"))
+}
+
+/// Gets the file where progress through the lessons is stored.
+fn lesson_progress_file() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("lesson_progress.txt"))
+}
+
+/// Gets the file where the history of test results is stored.
+fn history_file() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("history.json"))
+}
+
+/// Gets the file where the Markov model for the most recent corpus is cached.
+fn markov_model_file() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("markov_model.txt"))
+}
+
+/// Loads the built-in keyboard layouts, followed by any custom layouts.
+///
+/// Custom layouts are read from `.layout` files in the layouts directory. Files that can't be
+/// loaded, or that reuse the name of another layout, are skipped.
+pub fn load_keyboard_layouts() -> Vec<KeyboardLayout> {
+    let mut layouts: Vec<_> = BuiltinLayout::ALL_LAYOUTS
+        .iter()
+        .map(|layout| layout.layout())
+        .collect();
+
+    let entries = match layouts_dir().map(fs::read_dir) {
+        Some(Ok(entries)) => entries,
+        _ => return layouts,
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().map_or(false, |ext| ext == "layout"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        if let Ok(layout) = KeyboardLayout::load(&path) {
+            if layouts.iter().all(|l| l.name() != layout.name()) {
+                layouts.push(layout);
+            }
+        }
+    }
+
+    layouts
+}
+
+/// Loads any custom curricula.
+///
+/// Curricula are read from `.lessons` files in the lessons directory. Files that can't be loaded,
+/// or that reuse the name of another curriculum, are skipped.
+pub fn load_curricula() -> Vec<Curriculum> {
+    let mut curricula: Vec<Curriculum> = Vec::new();
+
+    let entries = match lessons_dir().map(fs::read_dir) {
+        Some(Ok(entries)) => entries,
+        _ => return curricula,
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().map_or(false, |ext| ext == "lessons"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        if let Ok(curriculum) = Curriculum::load(&path) {
+            if curricula.iter().all(|c| c.name() != curriculum.name()) {
+                curricula.push(curriculum);
+            }
+        }
+    }
+
+    curricula
+}
+
+/// Loads the progress through the lessons, which is empty if none has been saved.
+pub fn load_lesson_progress() -> LessonProgress {
+    lesson_progress_file()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|text| LessonProgress::parse(&text))
+        .unwrap_or_default()
+}
+
+/// Saves the progress through the lessons, replacing any that was previously saved.
+pub fn save_lesson_progress(progress: &LessonProgress) -> io::Result<()> {
+    let path = lesson_progress_file()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No data directory is available"))?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, progress.encode())
+}
+
+/// Loads the history of test results, which is empty if none has been saved or it can't be read.
+pub fn load_history() -> History {
+    history_file()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+/// Saves the history of test results, replacing any that was previously saved.
+pub fn save_history(history: &History) -> io::Result<()> {
+    let path = history_file()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No data directory is available"))?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, serde_json::to_string(history)?)
+}
+
+/// Loads the cached Markov model for the most recent corpus, if there is one.
+pub fn load_markov_model() -> Option<MarkovModel> {
+    markov_model_file().and_then(|path| MarkovModel::load(path).ok())
+}
+
+/// Trains a Markov model on a corpus, reusing the cached model if it was trained on the same
+/// corpus.
+///
+/// The new model replaces the cached one, so it is loaded again on the next run.
+pub fn train_markov_model(corpus: &str) -> MarkovModel {
+    match markov_model_file() {
+        Some(path) => MarkovModel::load_or_train(corpus, markov::DEFAULT_ORDER, path),
+        None => MarkovModel::train(corpus, markov::DEFAULT_ORDER),
+    }
+}
+
+/// Loads the recently used custom texts, from newest to oldest.
+pub fn load_custom_texts() -> Vec<String> {
+    let dir = match custom_texts_dir() {
+        Some(dir) => dir,
+        None => return Vec::new(),
+    };
+
+    let mut texts = Vec::new();
+    for i in 0..MAX_CUSTOM_TEXTS {
+        match fs::read_to_string(dir.join(format!("{}.txt", i))) {
+            Ok(text) => texts.push(text),
+            Err(_) => break,
+        }
+    }
+
+    texts
+}
+
+/// Saves the recently used custom texts, replacing any that were previously saved.
+pub fn save_custom_texts(texts: &[String]) -> io::Result<()> {
+    let dir = custom_texts_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No data directory is available"))?;
+
+    fs::create_dir_all(&dir)?;
+
+    for (i, text) in texts.iter().take(MAX_CUSTOM_TEXTS).enumerate() {
+        fs::write(dir.join(format!("{}.txt", i)), text)?;
+    }
+
+    // Remove any older texts, so they aren't loaded again
+    for i in texts.len()..MAX_CUSTOM_TEXTS {
+        let path = dir.join(format!("{}.txt", i));
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Exports test results to a file, using JSON if the file has a `.json` extension and CSV
+/// otherwise.
+pub fn export_results(path: &Path, results: &[TestResult]) -> io::Result<()> {
+    let contents = if path.extension().map_or(false, |ext| ext == "json") {
+        export::to_json(results)?
+    } else {
+        export::to_csv(results)
+    };
+
+    fs::write(path, contents)
+}
@@ -1,6 +1,26 @@
-use typetest_core::stats::TestStats;
+use std::sync::Arc;
+
+use typetest_core::{
+    history::TestResult,
+    lessons::LessonId,
+    modes::{ConfidenceMode, FailConditions, StopOnError},
+    replay::Replay,
+    stats::{scoring::Scoring, MissedWord},
+    word_generators::{
+        code::CodeLanguage,
+        custom::CustomTextOptions,
+        letter_set::LetterSetOptions,
+        markov::{MarkovMode, MarkovModel},
+        random::RandomWordOptions,
+    },
+};
 use typetest_themes::Theme;
 
+use self::typing_test::GeneratorKind;
+
+pub mod history;
+pub mod lessons;
+pub mod replay;
 pub mod results;
 pub mod settings;
 pub mod typing_test;
@@ -11,6 +31,9 @@ pub enum View {
     TypingTest,
     Results,
     Settings,
+    History,
+    Replay,
+    Lessons,
 }
 
 /// Represents an application-wide action which can be signalled from a view.
@@ -18,11 +41,37 @@ pub enum View {
 pub enum Action {
     ChangeTheme(Theme),
     ChangeView(View),
+    ChangeKeyboardLayout(String),
+    ChangeEmulatedLayout(Option<String>),
 
     // TODO: This should be some kind of "typing settings changed" structure, not an individual one
-    ChangeTimeLength(u64),
+    ChangeTimeLength(Option<u64>),
+    ChangeGeneratorKind(GeneratorKind),
+    ChangeRandomWordOptions(RandomWordOptions),
+    ChangeCodeLanguage(CodeLanguage),
+    ChangeCustomText(String),
+    ChangeCustomTextOptions(CustomTextOptions),
+    ChangeLetterSet(String),
+    ChangeLetterSetOptions(LetterSetOptions),
+    ChangeMarkovModel(Arc<MarkovModel>),
+    ChangeMarkovMode(MarkovMode),
+    PasteCustomText,
+    ChangeStopOnError(StopOnError),
+    ChangeConfidenceMode(ConfidenceMode),
+    ChangeFailConditions(FailConditions),
+    ChangeScoring(Scoring),
 
-    DisplayResults(TestStats),
+    DisplayResults(Box<TestResult>),
+    ImportResults(Vec<TestResult>),
+    ShowReplay(Replay),
+    PracticeMissedWords(Vec<MissedWord>),
+    StartLesson(LessonId),
     RetryTest,
     NextTest,
 }
+
+/// Formats the provided number of seconds into the mm:ss format.
+#[inline]
+pub fn format_time_mm_ss(seconds: u64) -> String {
+    format!("{:0>2}:{:0>2}", seconds / 60, seconds % 60)
+}
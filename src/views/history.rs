@@ -0,0 +1,332 @@
+use iced::{
+    button, pick_list, scrollable, Align, Button, Column, HorizontalAlignment, PickList, Row,
+    Scrollable, Text,
+};
+use typetest_core::{
+    history::{
+        import::{self, ImportReport, ImportSource},
+        History, TestResult,
+    },
+    replay::Replay,
+};
+use typetest_themes::ApplicationTheme;
+
+use crate::storage;
+
+use super::{format_time_mm_ss, replay::open_replay, Action, View};
+
+/// Represents the state for the history view.
+pub struct HistoryState {
+    results: Vec<TestResult>,
+    status: Option<String>,
+    skipped_records: Vec<String>,
+    import_source: ImportSource,
+
+    export_button: button::State,
+    import_button: button::State,
+    open_replay_button: button::State,
+    import_source_pick_list: pick_list::State<ImportSource>,
+    back_button: button::State,
+    results_scrollable: scrollable::State,
+    skipped_scrollable: scrollable::State,
+}
+
+/// Represents the messages used by the history view.
+#[derive(Clone, Debug)]
+pub enum HistoryMessage {
+    Export,
+    Exported(Option<Result<String, String>>),
+    ImportSourceChanged(ImportSource),
+    Import,
+    Imported(Option<Result<ImportReport, String>>),
+    OpenReplay,
+    ReplayOpened(Option<Result<Replay, String>>),
+    Action(Action),
+}
+
+impl HistoryState {
+    pub fn new() -> Self {
+        Self {
+            results: Vec::new(),
+            status: None,
+            skipped_records: Vec::new(),
+            import_source: ImportSource::Monkeytype,
+
+            export_button: button::State::new(),
+            import_button: button::State::new(),
+            open_replay_button: button::State::new(),
+            import_source_pick_list: pick_list::State::default(),
+            back_button: button::State::new(),
+            results_scrollable: scrollable::State::new(),
+            skipped_scrollable: scrollable::State::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: HistoryMessage) -> iced::Command<HistoryMessage> {
+        match message {
+            HistoryMessage::Export => {
+                let results = self.results.clone();
+                return iced::Command::perform(
+                    export_results(results, "history"),
+                    HistoryMessage::Exported,
+                );
+            }
+            HistoryMessage::Exported(status) => {
+                self.skipped_records.clear();
+                self.status = export_status(status);
+            }
+            HistoryMessage::ImportSourceChanged(source) => self.import_source = source,
+            HistoryMessage::Import => {
+                return iced::Command::perform(
+                    import_results(self.import_source),
+                    HistoryMessage::Imported,
+                );
+            }
+            HistoryMessage::Imported(Some(Ok(report))) => {
+                self.status = Some(format!(
+                    "Imported {} results, skipped {} records",
+                    report.results.len(),
+                    report.skipped.len()
+                ));
+                self.skipped_records = report.skipped.iter().map(|s| s.to_string()).collect();
+
+                return iced::Command::perform(async move { report.results }, |results| {
+                    HistoryMessage::Action(Action::ImportResults(results))
+                });
+            }
+            HistoryMessage::Imported(Some(Err(e))) => {
+                self.skipped_records.clear();
+                self.status = Some(format!("Import failed: {}", e));
+            }
+            HistoryMessage::OpenReplay => {
+                return iced::Command::perform(open_replay(), HistoryMessage::ReplayOpened);
+            }
+            HistoryMessage::ReplayOpened(Some(Ok(replay))) => {
+                return iced::Command::perform(async move { replay }, |replay| {
+                    HistoryMessage::Action(Action::ShowReplay(replay))
+                });
+            }
+            HistoryMessage::ReplayOpened(Some(Err(e))) => {
+                self.skipped_records.clear();
+                self.status = Some(format!("Failed to open replay: {}", e));
+            }
+            _ => {}
+        }
+
+        iced::Command::none()
+    }
+
+    pub fn view(&mut self, theme: &Box<dyn ApplicationTheme>) -> iced::Element<HistoryMessage> {
+        let title = Text::new("History").size(28);
+
+        let export_button = {
+            let tmp = Button::new(
+                &mut self.export_button,
+                Text::new("Export History").horizontal_alignment(HorizontalAlignment::Center),
+            )
+            .style(theme);
+
+            if self.results.is_empty() {
+                tmp
+            } else {
+                tmp.on_press(HistoryMessage::Export)
+            }
+        };
+
+        let back_button = Button::new(
+            &mut self.back_button,
+            Text::new("Back").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .min_width(100)
+        .style(theme)
+        .on_press(HistoryMessage::Action(Action::ChangeView(View::TypingTest)));
+
+        let import_source_pick_list = PickList::new(
+            &mut self.import_source_pick_list,
+            &ImportSource::ALL_SOURCES[..],
+            Some(self.import_source),
+            HistoryMessage::ImportSourceChanged,
+        )
+        .style(theme);
+
+        let import_button = Button::new(
+            &mut self.import_button,
+            Text::new("Import...").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .style(theme)
+        .on_press(HistoryMessage::Import);
+
+        let open_replay_button = Button::new(
+            &mut self.open_replay_button,
+            Text::new("Open Replay...").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .style(theme)
+        .on_press(HistoryMessage::OpenReplay);
+
+        let controls = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(back_button)
+            .push(export_button)
+            .push(import_source_pick_list)
+            .push(import_button)
+            .push(open_replay_button);
+
+        let mut history = Column::new()
+            .align_items(Align::Center)
+            .spacing(20)
+            .push(title);
+
+        if self.results.is_empty() {
+            history = history.push(Text::new("No tests have been completed yet!"));
+        } else {
+            let columns = (
+                Column::new().align_items(Align::End).spacing(5),
+                Column::new().align_items(Align::End).spacing(5),
+                Column::new().align_items(Align::End).spacing(5),
+                Column::new().align_items(Align::End).spacing(5),
+            );
+
+            // Newest results are shown first
+            let (tests, wpms, accuracies, lengths) = self.results.iter().enumerate().rev().fold(
+                columns,
+                |(tests, wpms, accuracies, lengths), (i, result)| {
+                    let (wpm, accuracy, length) = summarise(result);
+                    (
+                        tests.push(Text::new(match &result.imported {
+                            Some(imported) => format!("Test {} ({}):", i + 1, imported.source),
+                            None if result.practice => format!("Test {} (Practice):", i + 1),
+                            None => format!("Test {}:", i + 1),
+                        })),
+                        wpms.push(Text::new(wpm)),
+                        accuracies.push(Text::new(accuracy)),
+                        lengths.push(Text::new(length)),
+                    )
+                },
+            );
+
+            history = history.push(
+                Scrollable::new(&mut self.results_scrollable)
+                    .align_items(Align::Center)
+                    .padding(20)
+                    .style(theme)
+                    .max_height(300)
+                    .push(
+                        Row::new()
+                            .spacing(20)
+                            .push(tests)
+                            .push(wpms)
+                            .push(accuracies)
+                            .push(lengths),
+                    ),
+            );
+        }
+
+        if let Some(status) = &self.status {
+            history = history.push(Text::new(status.as_str()));
+        }
+
+        if !self.skipped_records.is_empty() {
+            let skipped_records = self
+                .skipped_records
+                .iter()
+                .fold(Column::new().spacing(5), |column, record| {
+                    column.push(Text::new(record.as_str()).size(16))
+                });
+
+            history = history.push(
+                Scrollable::new(&mut self.skipped_scrollable)
+                    .style(theme)
+                    .max_height(100)
+                    .push(skipped_records),
+            );
+        }
+
+        history.push(controls).into()
+    }
+
+    /// Updates the results shown in the history.
+    pub fn update_history(&mut self, history: &History) {
+        self.results = history.results().to_vec();
+    }
+
+    /// Shows that the history couldn't be saved.
+    pub fn history_not_saved(&mut self, error: std::io::Error) {
+        self.status = Some(format!("Failed to save history: {}", error));
+    }
+}
+
+/// Prompts the user to pick a file, then exports the results to it as CSV or JSON.
+///
+/// Returns the path that was written to, or `None` if no file was picked.
+pub async fn export_results(
+    results: Vec<TestResult>,
+    file_name: &str,
+) -> Option<Result<String, String>> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("CSV Files", &["csv"])
+        .add_filter("JSON Files", &["json"])
+        .set_file_name(&format!("{}.csv", file_name))
+        .save_file()
+        .await?;
+
+    Some(
+        storage::export_results(file.path(), &results)
+            .map(|_| file.path().display().to_string())
+            .map_err(|e| e.to_string()),
+    )
+}
+
+/// Prompts the user to pick a CSV or JSON export from another site, then imports the results
+/// from it.
+///
+/// Returns `None` if no file was picked.
+async fn import_results(source: ImportSource) -> Option<Result<ImportReport, String>> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("Exported Results", &["csv", "json"])
+        .pick_file()
+        .await?;
+
+    let contents = match std::fs::read_to_string(file.path()) {
+        Ok(contents) => contents,
+        Err(e) => return Some(Err(e.to_string())),
+    };
+
+    let report = if file.path().extension().map_or(false, |ext| ext == "json") {
+        import::import_json(source, &contents)
+    } else {
+        import::import_csv(source, &contents)
+    };
+
+    Some(report.map_err(|e| e.to_string()))
+}
+
+/// Describes the outcome of exporting results, or `None` if the export was cancelled.
+pub fn export_status(status: Option<Result<String, String>>) -> Option<String> {
+    match status? {
+        Ok(path) => Some(format!("Exported to {}", path)),
+        Err(e) => Some(format!("Export failed: {}", e)),
+    }
+}
+
+/// Summarises the WPM, accuracy and length of a test result.
+fn summarise(result: &TestResult) -> (String, String, String) {
+    let checkpoint = match result.stats.get_latest_checkpoint() {
+        Some(checkpoint) => checkpoint,
+        None => return (String::from("- WPM"), String::from("-"), String::from("-")),
+    };
+
+    let wpm = match result.scoring.wpm(checkpoint) {
+        Some(wpm) => format!("{} WPM", wpm),
+        None => String::from("- WPM"),
+    };
+
+    let accuracy = match result.scoring.accuracy(&result.stats, checkpoint) {
+        Some(accuracy) => format!("{:.2}%", accuracy),
+        None => String::from("-"),
+    };
+
+    let length = format_time_mm_ss(checkpoint.elapsed.as_secs());
+
+    (wpm, accuracy, length)
+}
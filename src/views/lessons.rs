@@ -0,0 +1,273 @@
+use iced::{
+    button, pick_list, scrollable, Align, Button, Column, HorizontalAlignment, Length, PickList,
+    Row, Scrollable, Text,
+};
+use typetest_core::{
+    history::TestResult,
+    layouts::KeyboardLayout,
+    lessons::{Curriculum, Lesson, LessonId, LessonProgress},
+};
+use typetest_themes::ApplicationTheme;
+
+use crate::storage;
+
+use super::{Action, View};
+
+/// Represents the state for the lessons view.
+pub struct LessonsState {
+    /// The built-in curriculum for the current keyboard layout, followed by any custom curricula.
+    curricula: Vec<Curriculum>,
+    curriculum_names: Vec<String>,
+    selected_curriculum: String,
+    progress: LessonProgress,
+    status: Option<String>,
+
+    curriculum_pick_list: pick_list::State<String>,
+    lesson_buttons: Vec<button::State>,
+    back_button: button::State,
+    lessons_scrollable: scrollable::State,
+}
+
+/// Represents the messages used by the lessons view.
+#[derive(Clone, Debug)]
+pub enum LessonsMessage {
+    CurriculumChanged(String),
+    Action(Action),
+}
+
+impl LessonsState {
+    pub fn new(custom_curricula: Vec<Curriculum>, layout: &KeyboardLayout) -> Self {
+        let mut state = Self {
+            curricula: custom_curricula,
+            curriculum_names: Vec::new(),
+            selected_curriculum: String::new(),
+            progress: storage::load_lesson_progress(),
+            status: None,
+
+            curriculum_pick_list: pick_list::State::default(),
+            lesson_buttons: Vec::new(),
+            back_button: button::State::new(),
+            lessons_scrollable: scrollable::State::new(),
+        };
+
+        let builtin = Curriculum::for_layout(layout);
+        state.curricula.retain(|c| c.name() != builtin.name());
+        state.curricula.insert(0, builtin);
+        state.curricula_changed();
+        state
+    }
+
+    pub fn update(&mut self, message: LessonsMessage) -> iced::Command<LessonsMessage> {
+        if let LessonsMessage::CurriculumChanged(name) = message {
+            self.selected_curriculum = name;
+        }
+
+        iced::Command::none()
+    }
+
+    pub fn view(&mut self, theme: &Box<dyn ApplicationTheme>) -> iced::Element<LessonsMessage> {
+        let title = Text::new("Lessons").size(28);
+
+        let curriculum_pick_list = PickList::new(
+            &mut self.curriculum_pick_list,
+            &self.curriculum_names,
+            Some(self.selected_curriculum.clone()),
+            LessonsMessage::CurriculumChanged,
+        )
+        .style(theme);
+
+        let back_button = Button::new(
+            &mut self.back_button,
+            Text::new("Back").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .min_width(100)
+        .style(theme)
+        .on_press(LessonsMessage::Action(Action::ChangeView(View::TypingTest)));
+
+        let controls = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(back_button)
+            .push(curriculum_pick_list);
+
+        let selected_curriculum = &self.selected_curriculum;
+        let curriculum = self
+            .curricula
+            .iter()
+            .find(|c| c.name() == selected_curriculum)
+            .unwrap_or(&self.curricula[0]);
+
+        let progress = &self.progress;
+        let passed = progress.passed(curriculum.name());
+
+        let lessons = curriculum
+            .lessons()
+            .iter()
+            .zip(self.lesson_buttons.iter_mut())
+            .enumerate()
+            .fold(
+                Column::new().spacing(10),
+                |column, (index, (lesson, button_state))| {
+                    let id = LessonId {
+                        curriculum: curriculum.name().to_string(),
+                        index,
+                    };
+
+                    let start_button = {
+                        let tmp = Button::new(
+                            button_state,
+                            Text::new(if index < passed { "Practise" } else { "Start" })
+                                .horizontal_alignment(HorizontalAlignment::Center),
+                        )
+                        .min_width(100)
+                        .style(theme);
+
+                        if progress.is_unlocked(&id) {
+                            tmp.on_press(LessonsMessage::Action(Action::StartLesson(id)))
+                        } else {
+                            tmp
+                        }
+                    };
+
+                    let status = if index < passed {
+                        "Passed"
+                    } else if index == passed {
+                        "Unlocked"
+                    } else {
+                        "Locked"
+                    };
+
+                    column.push(
+                        Row::new()
+                            .align_items(Align::Center)
+                            .spacing(20)
+                            .push(
+                                Text::new(format!("{}. {}", index + 1, lesson.name()))
+                                    .width(Length::Units(250)),
+                            )
+                            .push(
+                                Text::new(lesson.new_letters().iter().collect::<String>())
+                                    .width(Length::Units(120)),
+                            )
+                            .push(Text::new(format_targets(lesson)).width(Length::Units(150)))
+                            .push(Text::new(status).width(Length::Units(80)))
+                            .push(start_button),
+                    )
+                },
+            );
+
+        let mut view = Column::new()
+            .align_items(Align::Center)
+            .spacing(20)
+            .push(title)
+            .push(
+                Text::new(format!(
+                    "{} of {} lessons passed",
+                    passed.min(curriculum.lessons().len()),
+                    curriculum.lessons().len()
+                ))
+                .size(18),
+            )
+            .push(
+                Scrollable::new(&mut self.lessons_scrollable)
+                    .align_items(Align::Center)
+                    .padding(20)
+                    .style(theme)
+                    .max_height(350)
+                    .push(lessons),
+            );
+
+        if let Some(status) = &self.status {
+            view = view.push(Text::new(status.as_str()));
+        }
+
+        view.push(controls).into()
+    }
+
+    /// Replaces the built-in curriculum with the one for a new keyboard layout.
+    pub fn update_keyboard_layout(&mut self, layout: &KeyboardLayout) {
+        let was_builtin = self.selected_curriculum == self.curricula[0].name();
+
+        self.curricula[0] = Curriculum::for_layout(layout);
+        if was_builtin {
+            self.selected_curriculum = self.curricula[0].name().to_string();
+        }
+
+        self.curricula_changed();
+    }
+
+    /// Finds a lesson in one of the curricula.
+    pub fn find_lesson(&self, id: &LessonId) -> Option<&Lesson> {
+        self.curricula.iter().find_map(|c| c.lesson(id))
+    }
+
+    /// Records the result of a test typed for a lesson, unlocking the next lesson if the result
+    /// meets the lesson's targets.
+    ///
+    /// Returns a message describing how the lesson went, or `None` if the test wasn't for a
+    /// lesson.
+    pub fn record_result(&mut self, result: &TestResult) -> Option<String> {
+        let id = result.lesson.as_ref()?;
+        let lesson = self.find_lesson(id)?.clone();
+
+        if !lesson.is_passed(result) {
+            return Some(format!(
+                "Reach {} to pass {}",
+                format_targets(&lesson),
+                lesson.name()
+            ));
+        }
+
+        if !self.progress.pass(id) {
+            return Some(format!("Passed {} again", lesson.name()));
+        }
+
+        self.status = storage::save_lesson_progress(&self.progress)
+            .err()
+            .map(|e| format!("Failed to save lesson progress: {}", e));
+
+        let has_next_lesson = self
+            .find_lesson(&LessonId {
+                curriculum: id.curriculum.clone(),
+                index: id.index + 1,
+            })
+            .is_some();
+
+        Some(if has_next_lesson {
+            format!("Passed {}! The next lesson is unlocked.", lesson.name())
+        } else {
+            format!("Passed {}! Every lesson has been passed.", lesson.name())
+        })
+    }
+
+    /// Updates the curriculum names and lesson buttons after the curricula change.
+    fn curricula_changed(&mut self) {
+        self.curriculum_names = self
+            .curricula
+            .iter()
+            .map(|c| c.name().to_string())
+            .collect();
+
+        if !self.curriculum_names.contains(&self.selected_curriculum) {
+            self.selected_curriculum = self.curriculum_names[0].clone();
+        }
+
+        let max_lessons = self
+            .curricula
+            .iter()
+            .map(|c| c.lessons().len())
+            .max()
+            .unwrap_or(0);
+        self.lesson_buttons
+            .resize_with(max_lessons, button::State::new);
+    }
+}
+
+/// Formats the WPM and accuracy needed to pass a lesson.
+fn format_targets(lesson: &Lesson) -> String {
+    format!(
+        "{} WPM, {:.0}%",
+        lesson.target_wpm(),
+        lesson.target_accuracy()
+    )
+}
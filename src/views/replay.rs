@@ -0,0 +1,281 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use iced::{button, pick_list, Align, Button, Column, HorizontalAlignment, PickList, Row, Text};
+use typetest_core::{
+    replay::{Replay, ReplayFrame},
+    word_generators::TAB_WIDTH,
+};
+use typetest_themes::{ApplicationTheme, Theme};
+
+use crate::widgets::measured_lines::LineMetrics;
+
+use super::{
+    format_time_mm_ss,
+    typing_test::{blank_line, line_of_displayed_words, measured_lines, MAX_CHARS},
+    Action, View,
+};
+
+/// The file extension used for saved replays.
+const REPLAY_EXTENSION: &str = "replay";
+
+/// Represents the state for the replay view.
+pub struct ReplayState {
+    replay: Replay,
+    frame: ReplayFrame,
+    return_view: View,
+
+    position: Duration,
+    playing: bool,
+    speed: PlaybackSpeed,
+    last_tick: Option<Instant>,
+    line_metrics: LineMetrics,
+
+    play_button: button::State,
+    restart_button: button::State,
+    back_button: button::State,
+    speed_pick_list: pick_list::State<PlaybackSpeed>,
+}
+
+/// Represents the messages used by the replay view.
+#[derive(Clone, Debug)]
+pub enum ReplayMessage {
+    Tick(Instant),
+    TogglePlaying,
+    Restart,
+    SpeedChanged(PlaybackSpeed),
+    LinesMeasured(LineMetrics),
+    Action(Action),
+}
+
+/// Represents how many times faster than real time a replay is played.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlaybackSpeed(u32);
+
+impl ReplayState {
+    pub fn new() -> Self {
+        let replay = Replay::new();
+        let frame = replay.frame_at(Duration::default());
+
+        Self {
+            replay,
+            frame,
+            return_view: View::TypingTest,
+
+            position: Duration::default(),
+            playing: false,
+            speed: PlaybackSpeed(1),
+            last_tick: None,
+            line_metrics: LineMetrics::columns(MAX_CHARS),
+
+            play_button: button::State::new(),
+            restart_button: button::State::new(),
+            back_button: button::State::new(),
+            speed_pick_list: pick_list::State::default(),
+        }
+    }
+
+    pub fn update(&mut self, message: ReplayMessage) -> iced::Command<ReplayMessage> {
+        match message {
+            ReplayMessage::Tick(now) => {
+                if let Some(last_tick) = self.last_tick {
+                    let elapsed = now.checked_duration_since(last_tick).unwrap_or_default();
+                    self.position += elapsed * self.speed.0;
+                }
+
+                self.last_tick = Some(now);
+
+                if self.position >= self.replay.duration() {
+                    self.position = self.replay.duration();
+                    self.playing = false;
+                }
+            }
+            ReplayMessage::TogglePlaying => {
+                // Playing a finished replay starts it again
+                if !self.playing && self.position >= self.replay.duration() {
+                    self.position = Duration::default();
+                }
+
+                self.playing = !self.playing;
+                self.last_tick = None;
+            }
+            ReplayMessage::Restart => {
+                self.position = Duration::default();
+                self.last_tick = None;
+            }
+            ReplayMessage::SpeedChanged(speed) => self.speed = speed,
+            ReplayMessage::LinesMeasured(metrics) => self.line_metrics = metrics,
+            _ => {}
+        }
+
+        self.frame = self.replay.frame_at(self.position);
+        iced::Command::none()
+    }
+
+    pub fn view<'a>(
+        &'a mut self,
+        theme: &'a Box<dyn ApplicationTheme>,
+    ) -> iced::Element<'a, ReplayMessage> {
+        let frame = &self.frame;
+        let lines = frame.lines(&self.line_metrics.measure, self.line_metrics.max_width);
+
+        // Show the line with the current word (or the last line once every word is submitted),
+        // along with the next line
+        let line_starts: Vec<usize> = lines
+            .iter()
+            .scan(0, |start, line| {
+                let line_start = *start;
+                *start += line.len();
+                Some(line_start)
+            })
+            .collect();
+        let current_line = line_starts
+            .iter()
+            .rposition(|&start| start <= frame.current)
+            .unwrap_or(0);
+
+        let mut line_display = Column::new().spacing(5).push(blank_line());
+        for (i, line) in lines.iter().enumerate().skip(current_line).take(2) {
+            let current_pos = if i == current_line {
+                frame.current - line_starts[i]
+            } else {
+                line.len()
+            };
+
+            line_display = line_display.push(line_of_displayed_words(line, current_pos, theme));
+        }
+
+        let line_display = measured_lines(
+            line_display,
+            self.line_metrics,
+            ReplayMessage::LinesMeasured,
+        );
+
+        let input = Text::new(frame.input.replace('\t', &" ".repeat(TAB_WIDTH)))
+            .font(Theme::monospace_font())
+            .size(22);
+
+        let progress = Text::new(format!(
+            "{} / {}",
+            format_time_mm_ss(self.position.as_secs()),
+            format_time_mm_ss(self.replay.duration().as_secs())
+        ));
+
+        let play_button = Button::new(
+            &mut self.play_button,
+            Text::new(if self.playing { "Pause" } else { "Play" })
+                .horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .min_width(100)
+        .style(theme)
+        .on_press(ReplayMessage::TogglePlaying);
+
+        let restart_button = Button::new(
+            &mut self.restart_button,
+            Text::new("Restart").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .min_width(100)
+        .style(theme)
+        .on_press(ReplayMessage::Restart);
+
+        let speed_pick_list = PickList::new(
+            &mut self.speed_pick_list,
+            &PlaybackSpeed::ALL_SPEEDS[..],
+            Some(self.speed),
+            ReplayMessage::SpeedChanged,
+        )
+        .style(theme);
+
+        let back_button = Button::new(
+            &mut self.back_button,
+            Text::new("Back").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .min_width(100)
+        .style(theme)
+        .on_press(ReplayMessage::Action(Action::ChangeView(self.return_view)));
+
+        let controls = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(back_button)
+            .push(play_button)
+            .push(restart_button)
+            .push(speed_pick_list)
+            .push(progress);
+
+        Column::new()
+            .align_items(Align::Center)
+            .spacing(20)
+            .push(Text::new("Replay").size(28))
+            .push(line_display)
+            .push(input)
+            .push(controls)
+            .into()
+    }
+
+    pub fn subscription(&self) -> iced::Subscription<ReplayMessage> {
+        const TICK_DURATION: Duration = Duration::from_millis(50);
+
+        if self.playing {
+            iced::time::every(TICK_DURATION).map(ReplayMessage::Tick)
+        } else {
+            iced::Subscription::none()
+        }
+    }
+
+    /// Opens a replay from the start, returning to `return_view` when the replay is closed.
+    pub fn open(&mut self, replay: Replay, return_view: View) {
+        self.replay = replay;
+        self.return_view = return_view;
+        self.position = Duration::default();
+        self.playing = true;
+        self.last_tick = None;
+        self.frame = self.replay.frame_at(self.position);
+    }
+}
+
+impl PlaybackSpeed {
+    /// An array of all the playback speeds.
+    pub const ALL_SPEEDS: [PlaybackSpeed; 4] = [
+        PlaybackSpeed(1),
+        PlaybackSpeed(2),
+        PlaybackSpeed(4),
+        PlaybackSpeed(8),
+    ];
+}
+
+impl Display for PlaybackSpeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x Speed", self.0)
+    }
+}
+
+/// Prompts the user to pick where to save a replay, then saves it.
+///
+/// Returns the path that was written to, or `None` if no file was picked.
+pub async fn save_replay(replay: Replay) -> Option<Result<String, String>> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("Replays", &[REPLAY_EXTENSION])
+        .set_file_name(&format!("test.{}", REPLAY_EXTENSION))
+        .save_file()
+        .await?;
+
+    Some(
+        replay
+            .save(file.path())
+            .map(|_| file.path().display().to_string())
+            .map_err(|e| e.to_string()),
+    )
+}
+
+/// Prompts the user to pick a replay file, returning the replay if one was picked.
+pub async fn open_replay() -> Option<Result<Replay, String>> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("Replays", &[REPLAY_EXTENSION])
+        .pick_file()
+        .await?;
+
+    Some(Replay::load(file.path()).map_err(|e| e.to_string()))
+}
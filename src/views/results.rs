@@ -1,45 +1,165 @@
-use iced::{button, scrollable, Align, Button, Column, HorizontalAlignment, Row, Scrollable, Text};
-use typetest_core::stats::TestStats;
-use typetest_themes::ApplicationTheme;
+use std::{collections::HashMap, fmt::Display, hash::Hash, time::Duration};
 
-use super::Action;
+use iced::{
+    button, pick_list, scrollable, Align, Button, Canvas, Column, HorizontalAlignment, Length,
+    PickList, Row, Scrollable, Text,
+};
+use typetest_core::{
+    history::{History, TestResult},
+    layouts::{Finger, Hand, KeyboardLayout, Row as KeyRow},
+    modes::ConfidenceMode,
+    stats::{
+        fingers::FingerStats,
+        keys::KeyStats,
+        missed::{self, CharDiff},
+        ngrams::{self, NgramStats},
+        scoring::Scoring,
+        TestStats,
+    },
+};
+use typetest_themes::{ApplicationTheme, Theme, WordPalette};
+
+use crate::widgets::keyboard_heatmap::{HeatmapMetric, KeyboardHeatmap};
+
+use super::{
+    format_time_mm_ss,
+    history::{export_results, export_status},
+    replay::save_replay,
+    Action, View,
+};
+
+/// The length of the windows used for burst WPM and the slowest window.
+const WPM_WINDOW: Duration = Duration::from_secs(5);
+
+/// The number of bigrams shown in the slowest bigrams list.
+const SLOWEST_BIGRAMS: usize = 10;
+
+/// The width of the keyboard heatmap, in pixels.
+const HEATMAP_WIDTH: u16 = 450;
 
 /// Represents the state for the results view.
 pub struct ResultsState {
-    stats: TestStats,
+    result: TestResult,
+    test_key_stats: HashMap<char, KeyStats>,
+    history_key_stats: HashMap<char, KeyStats>,
+    keyboard_layout: KeyboardLayout,
+    finger_stats: FingerStats,
+    slowest_bigrams: Vec<(String, NgramStats)>,
+    personal_best: Option<u64>,
+    lesson_status: Option<String>,
 
     show_missed_words: bool,
+    show_heatmap: bool,
+    show_finger_stats: bool,
+    show_slowest_bigrams: bool,
+    heatmap_metric: HeatmapMetric,
+    heatmap_scope: HeatmapScope,
+    export_status: Option<String>,
 
     retry_button: button::State,
     next_test_button: button::State,
     toggle_missed_button: button::State,
+    toggle_heatmap_button: button::State,
+    toggle_finger_stats_button: button::State,
+    toggle_bigrams_button: button::State,
+    export_button: button::State,
+    history_button: button::State,
+    watch_replay_button: button::State,
+    save_replay_button: button::State,
+    practice_missed_button: button::State,
     missed_scrollable: scrollable::State,
+    heatmap_metric_pick_list: pick_list::State<HeatmapMetric>,
+    heatmap_scope_pick_list: pick_list::State<HeatmapScope>,
 }
 
 /// Represents the messages used by the results view.
 #[derive(Clone, Debug)]
 pub enum ResultsMessage {
     ToggleMissedWords,
+    ToggleHeatmap,
+    ToggleFingerStats,
+    ToggleSlowestBigrams,
+    HeatmapMetricChanged(HeatmapMetric),
+    HeatmapScopeChanged(HeatmapScope),
+    Export,
+    Exported(Option<Result<String, String>>),
+    SaveReplay,
+    ReplaySaved(Option<Result<String, String>>),
     Action(Action),
 }
 
+/// Represents which tests are included in the keyboard heatmap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeatmapScope {
+    CurrentTest,
+    AllTests,
+}
+
 impl ResultsState {
     pub fn new() -> Self {
         Self {
-            stats: TestStats::new(),
+            result: TestResult::new(TestStats::new(), ConfidenceMode::default()),
+            test_key_stats: HashMap::new(),
+            history_key_stats: HashMap::new(),
+            keyboard_layout: KeyboardLayout::default(),
+            finger_stats: FingerStats::default(),
+            slowest_bigrams: Vec::new(),
+            personal_best: None,
+            lesson_status: None,
 
             show_missed_words: false,
+            show_heatmap: false,
+            show_finger_stats: false,
+            show_slowest_bigrams: false,
+            heatmap_metric: HeatmapMetric::default(),
+            heatmap_scope: HeatmapScope::CurrentTest,
+            export_status: None,
 
             retry_button: button::State::new(),
             next_test_button: button::State::new(),
             toggle_missed_button: button::State::new(),
+            toggle_heatmap_button: button::State::new(),
+            toggle_finger_stats_button: button::State::new(),
+            toggle_bigrams_button: button::State::new(),
+            export_button: button::State::new(),
+            history_button: button::State::new(),
+            watch_replay_button: button::State::new(),
+            save_replay_button: button::State::new(),
+            practice_missed_button: button::State::new(),
             missed_scrollable: scrollable::State::new(),
+            heatmap_metric_pick_list: pick_list::State::default(),
+            heatmap_scope_pick_list: pick_list::State::default(),
         }
     }
 
     pub fn update(&mut self, message: ResultsMessage) -> iced::Command<ResultsMessage> {
         match message {
             ResultsMessage::ToggleMissedWords => self.show_missed_words = !self.show_missed_words,
+            ResultsMessage::ToggleHeatmap => self.show_heatmap = !self.show_heatmap,
+            ResultsMessage::ToggleFingerStats => self.show_finger_stats = !self.show_finger_stats,
+            ResultsMessage::ToggleSlowestBigrams => {
+                self.show_slowest_bigrams = !self.show_slowest_bigrams
+            }
+            ResultsMessage::HeatmapMetricChanged(metric) => self.heatmap_metric = metric,
+            ResultsMessage::HeatmapScopeChanged(scope) => self.heatmap_scope = scope,
+            ResultsMessage::Export => {
+                let results = vec![self.result.clone()];
+                return iced::Command::perform(
+                    export_results(results, "result"),
+                    ResultsMessage::Exported,
+                );
+            }
+            ResultsMessage::Exported(status) => self.export_status = export_status(status),
+            ResultsMessage::SaveReplay => {
+                let replay = self.result.stats.get_replay().clone();
+                return iced::Command::perform(save_replay(replay), ResultsMessage::ReplaySaved);
+            }
+            ResultsMessage::ReplaySaved(status) => {
+                self.export_status = status.map(|status| match status {
+                    Ok(path) => format!("Saved replay to {}", path),
+                    Err(e) => format!("Failed to save replay: {}", e),
+                })
+            }
             _ => {}
         }
 
@@ -48,16 +168,33 @@ impl ResultsState {
 
     pub fn view(&mut self, theme: &Box<dyn ApplicationTheme>) -> iced::Element<ResultsMessage> {
         let word_palette = theme.word_palette();
-        let checkpoint = self
-            .stats
-            .get_latest_checkpoint()
-            .expect("No test results to display!");
+        let stats = &self.result.stats;
+        let checkpoint = match stats.get_latest_checkpoint() {
+            Some(checkpoint) => checkpoint,
+            None => {
+                let next_test_button = Button::new(
+                    &mut self.next_test_button,
+                    Text::new("Next Test").horizontal_alignment(HorizontalAlignment::Center),
+                )
+                .min_width(100)
+                .style(theme)
+                .on_press(ResultsMessage::Action(Action::NextTest));
 
-        let wpm = Text::new(format!("{} WPM", checkpoint.effective_wpm())).size(30);
+                return Column::new()
+                    .align_items(Align::Center)
+                    .spacing(20)
+                    .push(Text::new("No test results to display!"))
+                    .push(next_test_button)
+                    .into();
+            }
+        };
+
+        let scoring = self.result.scoring;
+        let wpm = Text::new(format_wpm(scoring.wpm(checkpoint))).size(30);
 
         // Labels
         const LABEL_SPACING: u16 = 10;
-        let labels = Column::new()
+        let mut labels = Column::new()
             .align_items(Align::End)
             .spacing(LABEL_SPACING)
             .push(Text::new("Raw WPM:"))
@@ -68,7 +205,7 @@ impl ResultsState {
             .push(Text::new("Accuracy:"))
             .push(Text::new("Test Length:"));
 
-        let raw_wpm = Text::new(format!("{} WPM", checkpoint.raw_wpm()));
+        let raw_wpm = Text::new(format_wpm(checkpoint.raw_wpm()));
 
         let correct_chars =
             Text::new(checkpoint.correct_chars.to_string()).color(word_palette.correct);
@@ -80,10 +217,13 @@ impl ResultsState {
         let incorrect_words =
             Text::new(checkpoint.incorrect_words.to_string()).color(word_palette.incorrect);
 
-        let accuracy = Text::new(format!("{:.2}%", checkpoint.accuracy()));
+        let accuracy = Text::new(match scoring.accuracy(stats, checkpoint) {
+            Some(accuracy) => format!("{:.2}%", accuracy),
+            None => String::from("-"),
+        });
         let test_length = Text::new(format_time_mm_ss(checkpoint.elapsed.as_secs()));
 
-        let values = Column::new()
+        let mut values = Column::new()
             .align_items(Align::Start)
             .spacing(LABEL_SPACING)
             .push(raw_wpm)
@@ -94,6 +234,56 @@ impl ResultsState {
             .push(accuracy)
             .push(test_length);
 
+        if let Some(consistency) = stats.consistency() {
+            labels = labels.push(Text::new("Consistency:"));
+            values = values.push(Text::new(format!("{:.0}%", consistency)));
+        }
+
+        if let Some(window) = stats.fastest_window(WPM_WINDOW) {
+            labels = labels.push(Text::new("Burst WPM:"));
+            values = values.push(Text::new(format!("{} WPM", window.raw_wpm)));
+        }
+
+        if let Some(window) = stats.slowest_window(WPM_WINDOW) {
+            labels = labels.push(Text::new("Slowest Window:"));
+            values = values.push(Text::new(format!(
+                "{} WPM ({} - {})",
+                window.raw_wpm,
+                format_time_mm_ss(window.start.as_secs()),
+                format_time_mm_ss(window.end.as_secs())
+            )));
+        }
+
+        if checkpoint.blocked_attempts > 0 {
+            labels = labels.push(Text::new("Blocked Attempts:"));
+            values = values.push(
+                Text::new(checkpoint.blocked_attempts.to_string()).color(word_palette.incorrect),
+            );
+        }
+
+        if self.result.confidence_mode != ConfidenceMode::Off {
+            labels = labels.push(Text::new("Confidence Mode:"));
+            values = values.push(Text::new(self.result.confidence_mode.to_string()));
+        }
+
+        if scoring != Scoring::default() {
+            labels = labels.push(Text::new("Scoring:"));
+            values = values.push(Text::new(scoring.to_string()));
+        }
+
+        if let Some(layout) = &self.result.emulated_layout {
+            labels = labels.push(Text::new("Emulated Layout:"));
+            values = values.push(Text::new(layout.as_str()));
+        }
+
+        if self.result.practice {
+            labels = labels.push(Text::new("Practice Drill:"));
+            values = values.push(Text::new("Not counted towards personal best"));
+        } else if let Some(personal_best) = self.personal_best {
+            labels = labels.push(Text::new("Personal Best:"));
+            values = values.push(Text::new(format_wpm(Some(personal_best))));
+        }
+
         let stats_grid = Row::new().spacing(10).push(labels).push(values);
 
         let next_test_button = Button::new(
@@ -119,44 +309,181 @@ impl ResultsState {
             )
             .style(theme);
 
-            if self.stats.get_missed_words().is_empty() {
+            if stats.get_missed_words().is_empty() {
                 tmp
             } else {
                 tmp.on_press(ResultsMessage::ToggleMissedWords)
             }
         };
 
+        let toggle_heatmap_button = Button::new(
+            &mut self.toggle_heatmap_button,
+            Text::new("Toggle Key Heatmap").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .style(theme)
+        .on_press(ResultsMessage::ToggleHeatmap);
+
+        let toggle_finger_stats_button = Button::new(
+            &mut self.toggle_finger_stats_button,
+            Text::new("Toggle Finger Stats").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .style(theme)
+        .on_press(ResultsMessage::ToggleFingerStats);
+
+        let toggle_bigrams_button = {
+            let tmp = Button::new(
+                &mut self.toggle_bigrams_button,
+                Text::new("Toggle Slowest Bigrams")
+                    .horizontal_alignment(HorizontalAlignment::Center),
+            )
+            .style(theme);
+
+            if self.slowest_bigrams.is_empty() {
+                tmp
+            } else {
+                tmp.on_press(ResultsMessage::ToggleSlowestBigrams)
+            }
+        };
+
+        let export_button = Button::new(
+            &mut self.export_button,
+            Text::new("Export Result").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .style(theme)
+        .on_press(ResultsMessage::Export);
+
+        let history_button = Button::new(
+            &mut self.history_button,
+            Text::new("History").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .style(theme)
+        .on_press(ResultsMessage::Action(Action::ChangeView(View::History)));
+
+        let has_replay = !stats.get_replay().events().is_empty();
+        let watch_replay_button = {
+            let tmp = Button::new(
+                &mut self.watch_replay_button,
+                Text::new("Watch Replay").horizontal_alignment(HorizontalAlignment::Center),
+            )
+            .style(theme);
+
+            if has_replay {
+                tmp.on_press(ResultsMessage::Action(Action::ShowReplay(
+                    stats.get_replay().clone(),
+                )))
+            } else {
+                tmp
+            }
+        };
+
+        let save_replay_button = {
+            let tmp = Button::new(
+                &mut self.save_replay_button,
+                Text::new("Save Replay").horizontal_alignment(HorizontalAlignment::Center),
+            )
+            .style(theme);
+
+            if has_replay {
+                tmp.on_press(ResultsMessage::SaveReplay)
+            } else {
+                tmp
+            }
+        };
+
         let controls = Row::new()
             .align_items(Align::Center)
             .spacing(10)
             .push(next_test_button)
             .push(retry_button)
-            .push(toggle_missed_button);
+            .push(watch_replay_button)
+            .push(save_replay_button)
+            .push(export_button)
+            .push(history_button);
 
-        let mut results = Column::new()
+        let toggles = Row::new()
             .align_items(Align::Center)
-            .spacing(20)
-            .push(wpm)
-            .push(stats_grid);
+            .spacing(10)
+            .push(toggle_missed_button)
+            .push(toggle_heatmap_button)
+            .push(toggle_finger_stats_button)
+            .push(toggle_bigrams_button);
+
+        let mut results = Column::new().align_items(Align::Center).spacing(20);
+
+        if let Some(reason) = stats.get_fail_reason() {
+            let failed = Column::new()
+                .align_items(Align::Center)
+                .spacing(5)
+                .push(
+                    Text::new("Test Failed")
+                        .size(30)
+                        .color(word_palette.incorrect),
+                )
+                .push(Text::new(reason.to_string()));
+
+            results = results.push(failed);
+        }
+
+        results = results.push(wpm);
+
+        if let Some(status) = &self.lesson_status {
+            results = results.push(Text::new(status.as_str()));
+        }
+
+        results = results.push(stats_grid);
 
         if self.show_missed_words {
-            let missed_words = self.stats.get_missed_words();
-            if !missed_words.is_empty() {
-                // TODO: Grid widget?
-                let (missed_words, freq) = missed_words.iter().fold(
+            let missed_word_groups = stats.missed_word_groups();
+            if !missed_word_groups.is_empty() {
+                let (expected_col, count_col, attempts_col) = missed_word_groups.iter().fold(
                     (
+                        Column::new().align_items(Align::End),
                         Column::new().align_items(Align::End),
                         Column::new().align_items(Align::Start),
                     ),
-                    |(expected_col, actual_col), missed_word| {
+                    |(expected_col, count_col, attempts_col), group| {
+                        let mut attempts: Vec<&String> = Vec::new();
+                        for attempt in &group.attempts {
+                            if !attempts.contains(&attempt) {
+                                attempts.push(attempt);
+                            }
+                        }
+
+                        let attempts_row =
+                            attempts
+                                .iter()
+                                .fold(Row::new().spacing(15), |row, attempt| {
+                                    row.push(diff_of_missed_word(
+                                        &group.expected,
+                                        attempt,
+                                        &word_palette,
+                                    ))
+                                });
+
                         (
-                            expected_col.push(Text::new(&missed_word.expected)),
-                            actual_col.push(Text::new(&missed_word.actual)),
+                            expected_col
+                                .push(Text::new(&group.expected).font(Theme::monospace_font())),
+                            count_col.push(Text::new(format!("×{}", group.count()))),
+                            attempts_col.push(attempts_row),
                         )
                     },
                 );
 
-                let missed_words_grid = Row::new().spacing(10).push(missed_words).push(freq);
+                let missed_words_grid = Row::new()
+                    .spacing(10)
+                    .push(expected_col)
+                    .push(count_col)
+                    .push(attempts_col);
+
+                let practice_missed_button = Button::new(
+                    &mut self.practice_missed_button,
+                    Text::new("Practice Missed Words")
+                        .horizontal_alignment(HorizontalAlignment::Center),
+                )
+                .style(theme)
+                .on_press(ResultsMessage::Action(Action::PracticeMissedWords(
+                    stats.get_missed_words().to_vec(),
+                )));
 
                 results = results.push(
                     Scrollable::new(&mut self.missed_scrollable)
@@ -166,21 +493,241 @@ impl ResultsState {
                         .style(theme)
                         .max_height(200)
                         .push(Text::new("Missed Words").size(28))
+                        .push(practice_missed_button)
                         .push(missed_words_grid),
                 );
             }
         }
 
-        results.push(controls).into()
+        if self.show_heatmap {
+            let key_stats = match self.heatmap_scope {
+                HeatmapScope::CurrentTest => &self.test_key_stats,
+                HeatmapScope::AllTests => &self.history_key_stats,
+            };
+
+            let metric_pick_list = PickList::new(
+                &mut self.heatmap_metric_pick_list,
+                &HeatmapMetric::ALL_METRICS[..],
+                Some(self.heatmap_metric),
+                ResultsMessage::HeatmapMetricChanged,
+            )
+            .style(theme);
+
+            let scope_pick_list = PickList::new(
+                &mut self.heatmap_scope_pick_list,
+                &HeatmapScope::ALL_SCOPES[..],
+                Some(self.heatmap_scope),
+                ResultsMessage::HeatmapScopeChanged,
+            )
+            .style(theme);
+
+            let heatmap_options = Row::new()
+                .align_items(Align::Center)
+                .spacing(10)
+                .push(metric_pick_list)
+                .push(scope_pick_list);
+
+            let heatmap = Canvas::new(KeyboardHeatmap::new(
+                key_stats,
+                &self.keyboard_layout,
+                self.heatmap_metric,
+                theme.heatmap_palette(),
+            ))
+            .width(Length::Units(HEATMAP_WIDTH))
+            .height(Length::Units(
+                (HEATMAP_WIDTH as f32 / KeyboardHeatmap::ASPECT_RATIO) as u16,
+            ));
+
+            results = results.push(
+                Column::new()
+                    .align_items(Align::Center)
+                    .spacing(10)
+                    .push(heatmap_options)
+                    .push(heatmap),
+            );
+        }
+
+        if self.show_finger_stats {
+            let finger_stats = &self.finger_stats;
+            let tables = Row::new()
+                .spacing(30)
+                .push(key_stats_table(&Finger::ALL_FINGERS, &finger_stats.fingers))
+                .push(
+                    Column::new()
+                        .spacing(20)
+                        .push(key_stats_table(&Hand::ALL_HANDS, &finger_stats.hands))
+                        .push(key_stats_table(&KeyRow::ALL_ROWS, &finger_stats.rows)),
+                );
+
+            let same_finger_bigrams = Text::new(format!(
+                "Same Finger Bigrams: {} ({:.2}%)",
+                finger_stats.same_finger_bigrams,
+                finger_stats.same_finger_bigram_rate() * 100.0
+            ));
+
+            results = results.push(
+                Column::new()
+                    .align_items(Align::Center)
+                    .spacing(10)
+                    .push(Text::new("Finger Stats").size(28))
+                    .push(tables)
+                    .push(same_finger_bigrams),
+            );
+        }
+
+        if self.show_slowest_bigrams && !self.slowest_bigrams.is_empty() {
+            let columns = (
+                Column::new().align_items(Align::End).spacing(5),
+                Column::new().align_items(Align::End).spacing(5),
+                Column::new().align_items(Align::End).spacing(5),
+            );
+
+            let (bigrams, times, error_rates) = self.slowest_bigrams.iter().fold(
+                columns,
+                |(bigrams, times, error_rates), (bigram, s)| {
+                    let time = s.average_time().unwrap_or_default().as_millis();
+                    (
+                        bigrams.push(Text::new(format!("\"{}\"", bigram))),
+                        times.push(Text::new(format!("{} ms", time))),
+                        error_rates.push(
+                            Text::new(format!("{:.2}% errors", s.error_rate() * 100.0))
+                                .color(word_palette.incorrect),
+                        ),
+                    )
+                },
+            );
+
+            results = results.push(
+                Column::new()
+                    .align_items(Align::Center)
+                    .spacing(10)
+                    .push(Text::new("Slowest Bigrams").size(28))
+                    .push(
+                        Row::new()
+                            .spacing(10)
+                            .push(bigrams)
+                            .push(times)
+                            .push(error_rates),
+                    ),
+            );
+        }
+
+        if let Some(status) = &self.export_status {
+            results = results.push(Text::new(status.as_str()));
+        }
+
+        results.push(controls).push(toggles).into()
+    }
+
+    /// Displays a new result, where `layout` is the keyboard layout the test was typed on.
+    pub fn update_result(
+        &mut self,
+        result: TestResult,
+        history: &History,
+        layout: &KeyboardLayout,
+    ) {
+        self.finger_stats = result.stats.finger_stats(layout);
+        self.slowest_bigrams =
+            ngrams::slowest_ngrams(&result.stats.ngram_stats(2), SLOWEST_BIGRAMS)
+                .into_iter()
+                .map(|(bigram, s)| (bigram.to_string(), s))
+                .collect();
+        self.test_key_stats = result.stats.key_stats();
+        self.history_key_stats = history.key_stats();
+        self.personal_best = history.personal_best().and_then(|best| best.wpm());
+        self.export_status = None;
+        self.lesson_status = None;
+        self.result = result;
+    }
+
+    /// Sets the message describing how the lesson went, if the test was for a lesson.
+    pub fn update_lesson_status(&mut self, status: Option<String>) {
+        self.lesson_status = status;
+    }
+
+    /// Sets the keyboard layout used to label the keyboard heatmap.
+    pub fn update_keyboard_layout(&mut self, layout: KeyboardLayout) {
+        self.keyboard_layout = layout;
     }
+}
+
+impl HeatmapScope {
+    /// An array of all the heatmap scopes.
+    pub const ALL_SCOPES: [HeatmapScope; 2] = [HeatmapScope::CurrentTest, HeatmapScope::AllTests];
+}
 
-    pub fn update_stats(&mut self, stats: TestStats) {
-        self.stats = stats;
+impl Display for HeatmapScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            HeatmapScope::CurrentTest => f.write_str("This Test"),
+            HeatmapScope::AllTests => f.write_str("All Tests"),
+        }
     }
 }
 
-/// Formats the provided number of seconds into the mm:ss format.
-#[inline]
-fn format_time_mm_ss(seconds: u64) -> String {
-    format!("{:0>2}:{:0>2}", seconds / 60, seconds % 60)
+/// Builds a table showing the accuracy and average latency for each group of keys that was typed.
+fn key_stats_table<'a, T>(groups: &[T], stats: &HashMap<T, KeyStats>) -> Row<'a, ResultsMessage>
+where
+    T: Display + Eq + Hash,
+{
+    let columns = (
+        Column::new().align_items(Align::End).spacing(5),
+        Column::new().align_items(Align::End).spacing(5),
+        Column::new().align_items(Align::End).spacing(5),
+    );
+
+    let (names, accuracies, latencies) = groups
+        .iter()
+        .filter_map(|group| stats.get(group).map(|s| (group, s)))
+        .fold(columns, |(names, accuracies, latencies), (group, s)| {
+            let latency = match s.average_latency() {
+                Some(latency) => format!("{} ms", latency.as_millis()),
+                None => String::from("-"),
+            };
+
+            (
+                names.push(Text::new(format!("{}:", group))),
+                accuracies.push(Text::new(format!("{:.2}%", (1.0 - s.error_rate()) * 100.0))),
+                latencies.push(Text::new(latency)),
+            )
+        });
+
+    Row::new()
+        .spacing(10)
+        .push(names)
+        .push(accuracies)
+        .push(latencies)
+}
+
+/// Builds a row showing what was typed for a missed word, highlighting the incorrect letters.
+///
+/// Letters that were left out of the word are shown as underscores.
+fn diff_of_missed_word<'a>(
+    expected: &str,
+    actual: &str,
+    word_palette: &WordPalette,
+) -> Row<'a, ResultsMessage> {
+    missed::char_diff(expected, actual)
+        .into_iter()
+        .fold(Row::new(), |row, diff| {
+            let (c, color) = match diff {
+                CharDiff::Correct(c) => (c, word_palette.default),
+                CharDiff::Incorrect(c) | CharDiff::Extra(c) => (c, word_palette.incorrect),
+                CharDiff::Missing(_) => ('_', word_palette.incorrect),
+            };
+
+            row.push(
+                Text::new(c.to_string())
+                    .font(Theme::monospace_font())
+                    .color(color),
+            )
+        })
+}
+
+/// Formats a WPM value, which is undefined if no time has elapsed.
+fn format_wpm(wpm: Option<u64>) -> String {
+    match wpm {
+        Some(wpm) => format!("{} WPM", wpm),
+        None => String::from("- WPM"),
+    }
 }
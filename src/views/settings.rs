@@ -1,5 +1,11 @@
+mod code_generator;
+mod custom_text;
 mod global;
+mod letter_set;
+mod markov;
+mod modes;
 mod random_generator;
+mod scoring;
 
 use iced::{
     button, scrollable, Align, Button, Column, HorizontalAlignment, Length, Scrollable, Text,
@@ -7,8 +13,14 @@ use iced::{
 use typetest_themes::ApplicationTheme;
 
 use self::{
+    code_generator::{CodeGeneratorMessage, CodeGeneratorState},
+    custom_text::{CustomTextMessage, CustomTextState},
     global::{GlobalSettingsMessage, GlobalSettingsState},
+    letter_set::{LetterSetMessage, LetterSetState},
+    markov::{MarkovMessage, MarkovState},
+    modes::{ModesMessage, ModesState},
     random_generator::{RandomGeneratorMessage, RandomGeneratorState},
+    scoring::{ScoringMessage, ScoringState},
 };
 
 use super::{Action, View};
@@ -16,7 +28,13 @@ use super::{Action, View};
 /// Represents the state for the settings view.
 pub struct SettingsState {
     global_settings: GlobalSettingsState,
+    modes: ModesState,
+    scoring: ScoringState,
     random_generator: RandomGeneratorState,
+    code_generator: CodeGeneratorState,
+    custom_text: CustomTextState,
+    letter_set: LetterSetState,
+    markov: MarkovState,
 
     // Other Widgets
     scroll: scrollable::State,
@@ -27,15 +45,27 @@ pub struct SettingsState {
 #[derive(Clone, Debug)]
 pub enum SettingsMessage {
     GlobalSettings(GlobalSettingsMessage),
+    Modes(ModesMessage),
+    Scoring(ScoringMessage),
     RandomGenerator(RandomGeneratorMessage),
+    CodeGenerator(CodeGeneratorMessage),
+    CustomText(CustomTextMessage),
+    LetterSet(LetterSetMessage),
+    Markov(MarkovMessage),
     Action(Action),
 }
 
 impl SettingsState {
-    pub fn new() -> Self {
+    pub fn new(layout_names: Vec<String>) -> Self {
         Self {
-            global_settings: GlobalSettingsState::new(),
+            global_settings: GlobalSettingsState::new(layout_names),
+            modes: ModesState::new(),
+            scoring: ScoringState::new(),
             random_generator: RandomGeneratorState::new(),
+            code_generator: CodeGeneratorState::new(),
+            custom_text: CustomTextState::new(),
+            letter_set: LetterSetState::new(),
+            markov: MarkovState::new(),
 
             scroll: scrollable::State::new(),
             back_button: button::State::new(),
@@ -47,13 +77,36 @@ impl SettingsState {
             SettingsMessage::GlobalSettings(m) => {
                 self.global_settings.update(m).map(SettingsMessage::from)
             }
+            SettingsMessage::Modes(m) => self.modes.update(m).map(SettingsMessage::from),
+            SettingsMessage::Scoring(m) => self.scoring.update(m).map(SettingsMessage::from),
             SettingsMessage::RandomGenerator(m) => {
                 self.random_generator.update(m).map(SettingsMessage::from)
             }
+            SettingsMessage::CodeGenerator(m) => {
+                self.code_generator.update(m).map(SettingsMessage::from)
+            }
+            SettingsMessage::CustomText(m) => self.custom_text.update(m).map(SettingsMessage::from),
+            SettingsMessage::LetterSet(m) => self.letter_set.update(m).map(SettingsMessage::from),
+            SettingsMessage::Markov(m) => self.markov.update(m).map(SettingsMessage::from),
             _ => iced::Command::none(),
         }
     }
 
+    /// Sets the custom text to text that was pasted from the clipboard.
+    pub fn paste_custom_text(&mut self, text: String) {
+        self.custom_text.paste_text(text);
+    }
+
+    /// Gets the most recently used custom text, if there is one.
+    pub fn recent_custom_text(&self) -> Option<&str> {
+        self.custom_text.recent_text()
+    }
+
+    /// Notes that the Markov generator is using the model cached from a previous run.
+    pub fn use_cached_markov_model(&mut self) {
+        self.markov.use_cached_model();
+    }
+
     pub fn view(&mut self, theme: &Box<dyn ApplicationTheme>) -> iced::Element<SettingsMessage> {
         let back_button = Button::new(
             &mut self.back_button,
@@ -72,7 +125,13 @@ impl SettingsState {
             .width(Length::Fill)
             .style(theme)
             .push(self.global_settings.view(theme).map(SettingsMessage::from))
-            .push(self.random_generator.view(theme).map(SettingsMessage::from));
+            .push(self.modes.view(theme).map(SettingsMessage::from))
+            .push(self.scoring.view(theme).map(SettingsMessage::from))
+            .push(self.random_generator.view(theme).map(SettingsMessage::from))
+            .push(self.code_generator.view(theme).map(SettingsMessage::from))
+            .push(self.custom_text.view(theme).map(SettingsMessage::from))
+            .push(self.letter_set.view(theme).map(SettingsMessage::from))
+            .push(self.markov.view(theme).map(SettingsMessage::from));
 
         Column::new()
             .align_items(Align::Center)
@@ -0,0 +1,74 @@
+use iced::{pick_list, Align, Column, Command, Element, PickList, Row, Text};
+use typetest_core::word_generators::code::CodeLanguage;
+use typetest_themes::ApplicationTheme;
+
+use crate::views::Action;
+
+use super::SettingsMessage;
+
+/// Represents a message specific to the code generator settings view.
+#[derive(Clone, Debug)]
+pub enum CodeGeneratorMessage {
+    LanguageChanged(CodeLanguage),
+    Action(Action),
+}
+
+impl From<CodeGeneratorMessage> for SettingsMessage {
+    #[inline]
+    fn from(message: CodeGeneratorMessage) -> Self {
+        if let CodeGeneratorMessage::Action(a) = message {
+            SettingsMessage::Action(a)
+        } else {
+            SettingsMessage::CodeGenerator(message)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CodeGeneratorState {
+    language: CodeLanguage,
+    language_pick_list: pick_list::State<CodeLanguage>,
+}
+
+impl CodeGeneratorState {
+    pub fn new() -> CodeGeneratorState {
+        CodeGeneratorState {
+            language: CodeLanguage::default(),
+            language_pick_list: pick_list::State::default(),
+        }
+    }
+
+    pub fn update(&mut self, message: CodeGeneratorMessage) -> Command<CodeGeneratorMessage> {
+        match message {
+            CodeGeneratorMessage::LanguageChanged(language) => {
+                self.language = language;
+                Command::perform(async move { language }, |language| {
+                    CodeGeneratorMessage::Action(Action::ChangeCodeLanguage(language))
+                })
+            }
+            _ => Command::none(),
+        }
+    }
+
+    /// Builds the widget for code generator settings.
+    pub fn view(&mut self, theme: &Box<dyn ApplicationTheme>) -> Element<CodeGeneratorMessage> {
+        let title = Text::new("Code Generator Settings").size(28);
+
+        let language_label = Text::new("Language:");
+        let language_pick_list = PickList::new(
+            &mut self.language_pick_list,
+            &CodeLanguage::ALL_LANGUAGES[..],
+            Some(self.language),
+            CodeGeneratorMessage::LanguageChanged,
+        )
+        .style(theme);
+
+        let language = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(language_label)
+            .push(language_pick_list);
+
+        Column::new().spacing(10).push(title).push(language).into()
+    }
+}
@@ -0,0 +1,308 @@
+use std::fmt::Display;
+
+use iced::{
+    button, pick_list, text_input, Align, Button, Checkbox, Column, Command, Element, PickList,
+    Row, Text, TextInput,
+};
+use typetest_core::word_generators::custom::CustomTextOptions;
+use typetest_themes::ApplicationTheme;
+
+use crate::{
+    storage::{self, MAX_CUSTOM_TEXTS},
+    views::Action,
+};
+
+use super::SettingsMessage;
+
+/// The maximum number of characters shown when previewing a recent custom text.
+const PREVIEW_CHARS: usize = 30;
+
+/// Represents a message specific to the custom text settings view.
+#[derive(Clone, Debug)]
+pub enum CustomTextMessage {
+    TextChanged(String),
+    OpenFile,
+    FileOpened(Option<Result<String, String>>),
+    RecentTextSelected(usize),
+    UseText,
+    TextsSaved(Result<(), String>),
+
+    RepeatToggled(bool),
+    ShuffleToggled(bool),
+    WordLimitChanged(WordLimit),
+    Action(Action),
+}
+
+/// Represents an optional limit on the number of words taken from a custom text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WordLimit(Option<usize>);
+
+impl From<CustomTextMessage> for SettingsMessage {
+    #[inline]
+    fn from(message: CustomTextMessage) -> Self {
+        if let CustomTextMessage::Action(a) = message {
+            SettingsMessage::Action(a)
+        } else {
+            SettingsMessage::CustomText(message)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CustomTextState {
+    text: String,
+    recent_texts: Vec<String>,
+    status: Option<String>,
+    options: CustomTextOptions,
+
+    text_input: text_input::State,
+    paste_button: button::State,
+    open_file_button: button::State,
+    use_text_button: button::State,
+    recent_text_buttons: [button::State; MAX_CUSTOM_TEXTS],
+    word_limit_pick_list: pick_list::State<WordLimit>,
+}
+
+impl CustomTextState {
+    pub fn new() -> CustomTextState {
+        let recent_texts = storage::load_custom_texts();
+
+        CustomTextState {
+            text: recent_texts.first().cloned().unwrap_or_default(),
+            recent_texts,
+            status: None,
+            options: CustomTextOptions::default(),
+
+            text_input: text_input::State::new(),
+            paste_button: button::State::new(),
+            open_file_button: button::State::new(),
+            use_text_button: button::State::new(),
+            recent_text_buttons: Default::default(),
+            word_limit_pick_list: pick_list::State::default(),
+        }
+    }
+
+    pub fn update(&mut self, message: CustomTextMessage) -> Command<CustomTextMessage> {
+        match message {
+            CustomTextMessage::TextChanged(text) => self.text = text,
+            CustomTextMessage::OpenFile => {
+                return Command::perform(open_text_file(), CustomTextMessage::FileOpened)
+            }
+            CustomTextMessage::FileOpened(Some(Ok(text))) => self.text = text,
+            CustomTextMessage::FileOpened(Some(Err(e))) => {
+                self.status = Some(format!("Could not open file: {}", e));
+            }
+            CustomTextMessage::RecentTextSelected(i) => {
+                if let Some(text) = self.recent_texts.get(i) {
+                    self.text = text.clone();
+                    return self.use_text();
+                }
+            }
+            CustomTextMessage::UseText => return self.use_text(),
+            CustomTextMessage::TextsSaved(Err(e)) => {
+                self.status = Some(format!("Could not save custom texts: {}", e));
+            }
+            CustomTextMessage::RepeatToggled(repeat) => {
+                self.options.repeat = repeat;
+                return self.options_changed();
+            }
+            CustomTextMessage::ShuffleToggled(shuffle) => {
+                self.options.shuffle = shuffle;
+                return self.options_changed();
+            }
+            CustomTextMessage::WordLimitChanged(WordLimit(limit)) => {
+                self.options.word_limit = limit;
+                return self.options_changed();
+            }
+            _ => {}
+        }
+
+        Command::none()
+    }
+
+    /// Sets the custom text to text that was pasted from the clipboard.
+    pub fn paste_text(&mut self, text: String) {
+        self.text = text;
+    }
+
+    /// Gets the most recently used custom text, if there is one.
+    pub fn recent_text(&self) -> Option<&str> {
+        self.recent_texts.first().map(String::as_str)
+    }
+
+    /// Signals that the current text should be used for tests, remembering it for reuse.
+    fn use_text(&mut self) -> Command<CustomTextMessage> {
+        if self.text.trim().is_empty() {
+            self.status = Some(String::from("Custom text is empty!"));
+            return Command::none();
+        }
+
+        let text = self.text.clone();
+        self.recent_texts.retain(|t| *t != text);
+        self.recent_texts.insert(0, text.clone());
+        self.recent_texts.truncate(MAX_CUSTOM_TEXTS);
+
+        self.status = Some(format!(
+            "Using custom text ({} words)",
+            text.split_whitespace().count()
+        ));
+
+        let texts = self.recent_texts.clone();
+        Command::batch(vec![
+            Command::perform(async move { text }, |text| {
+                CustomTextMessage::Action(Action::ChangeCustomText(text))
+            }),
+            Command::perform(
+                async move { storage::save_custom_texts(&texts).map_err(|e| e.to_string()) },
+                CustomTextMessage::TextsSaved,
+            ),
+        ])
+    }
+
+    /// Signals that the custom text options have changed.
+    fn options_changed(&self) -> Command<CustomTextMessage> {
+        let options = self.options;
+        Command::perform(async move { options }, |options| {
+            CustomTextMessage::Action(Action::ChangeCustomTextOptions(options))
+        })
+    }
+
+    /// Builds the widget for custom text settings.
+    pub fn view(&mut self, theme: &Box<dyn ApplicationTheme>) -> Element<CustomTextMessage> {
+        const WORD_LIMIT_OPTIONS: [WordLimit; 6] = [
+            WordLimit(None),
+            WordLimit(Some(10)),
+            WordLimit(Some(25)),
+            WordLimit(Some(50)),
+            WordLimit(Some(100)),
+            WordLimit(Some(200)),
+        ];
+
+        let title = Text::new("Custom Text Settings").size(28);
+
+        let text_input = TextInput::new(
+            &mut self.text_input,
+            "Type or paste text here...",
+            &self.text,
+            CustomTextMessage::TextChanged,
+        )
+        .padding(5)
+        .style(theme);
+
+        let paste_button = Button::new(&mut self.paste_button, Text::new("Paste"))
+            .style(theme)
+            .on_press(CustomTextMessage::Action(Action::PasteCustomText));
+
+        let open_file_button = Button::new(&mut self.open_file_button, Text::new("Open File..."))
+            .style(theme)
+            .on_press(CustomTextMessage::OpenFile);
+
+        let use_text_button = Button::new(&mut self.use_text_button, Text::new("Use Text"))
+            .style(theme)
+            .on_press(CustomTextMessage::UseText);
+
+        let text_controls = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(paste_button)
+            .push(open_file_button)
+            .push(use_text_button);
+
+        let repeat = Checkbox::new(
+            self.options.repeat,
+            "Repeat Text",
+            CustomTextMessage::RepeatToggled,
+        )
+        .style(theme);
+
+        let shuffle = Checkbox::new(
+            self.options.shuffle,
+            "Shuffle Words",
+            CustomTextMessage::ShuffleToggled,
+        )
+        .style(theme);
+
+        let word_limit_label = Text::new("Word Limit:");
+        let word_limit_pick_list = PickList::new(
+            &mut self.word_limit_pick_list,
+            &WORD_LIMIT_OPTIONS[..],
+            Some(WordLimit(self.options.word_limit)),
+            CustomTextMessage::WordLimitChanged,
+        )
+        .style(theme);
+
+        let word_limit = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(word_limit_label)
+            .push(word_limit_pick_list);
+
+        let mut content = Column::new()
+            .spacing(10)
+            .push(title)
+            .push(text_input)
+            .push(text_controls);
+
+        if let Some(status) = &self.status {
+            content = content.push(Text::new(status.as_str()));
+        }
+
+        content = content.push(repeat).push(shuffle).push(word_limit);
+
+        if !self.recent_texts.is_empty() {
+            let recent_texts = self
+                .recent_texts
+                .iter()
+                .zip(self.recent_text_buttons.iter_mut())
+                .enumerate()
+                .fold(Column::new().spacing(5), |column, (i, (text, state))| {
+                    column.push(
+                        Button::new(state, Text::new(preview(text)))
+                            .style(theme)
+                            .on_press(CustomTextMessage::RecentTextSelected(i)),
+                    )
+                });
+
+            content = content.push(Text::new("Recent Texts:")).push(recent_texts);
+        }
+
+        content.into()
+    }
+}
+
+impl Display for WordLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(limit) => write!(f, "{} Words", limit),
+            None => f.write_str("Off"),
+        }
+    }
+}
+
+/// Prompts the user to pick a text file, returning its contents if one was picked.
+async fn open_text_file() -> Option<Result<String, String>> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("Text Files", &["txt"])
+        .pick_file()
+        .await?;
+
+    Some(std::fs::read_to_string(file.path()).map_err(|e| e.to_string()))
+}
+
+/// Shortens a custom text into a single line that fits on a button.
+fn preview(text: &str) -> String {
+    let mut words = text.split_whitespace();
+    let mut preview = words.next().unwrap_or_default().to_string();
+
+    for word in words {
+        if preview.chars().count() + word.chars().count() + 1 > PREVIEW_CHARS {
+            preview.push_str("...");
+            break;
+        }
+
+        preview.push(' ');
+        preview.push_str(word);
+    }
+
+    preview
+}
@@ -1,7 +1,9 @@
+use std::fmt::Display;
+
 use iced::{pick_list, Align, Column, Command, Element, PickList, Row, Text};
 use typetest_themes::{ApplicationTheme, Theme};
 
-use crate::views::Action;
+use crate::views::{typing_test::GeneratorKind, Action};
 
 use super::SettingsMessage;
 
@@ -10,14 +12,32 @@ use super::SettingsMessage;
 pub struct GlobalSettingsState {
     current_theme: Theme,
     theme_pick_list: pick_list::State<Theme>,
+
+    current_generator: GeneratorKind,
+    generator_pick_list: pick_list::State<GeneratorKind>,
+
+    layout_names: Vec<String>,
+    current_layout: String,
+    layout_pick_list: pick_list::State<String>,
+
+    emulated_layouts: Vec<EmulatedLayout>,
+    current_emulated_layout: EmulatedLayout,
+    emulated_layout_pick_list: pick_list::State<EmulatedLayout>,
 }
 
 #[derive(Clone, Debug)]
 pub enum GlobalSettingsMessage {
     ThemeChanged(Theme),
+    GeneratorChanged(GeneratorKind),
+    LayoutChanged(String),
+    EmulatedLayoutChanged(EmulatedLayout),
     Action(Action),
 }
 
+/// Represents the name of an optional keyboard layout to emulate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmulatedLayout(Option<String>);
+
 impl From<GlobalSettingsMessage> for SettingsMessage {
     #[inline]
     fn from(message: GlobalSettingsMessage) -> Self {
@@ -30,10 +50,26 @@ impl From<GlobalSettingsMessage> for SettingsMessage {
 }
 
 impl GlobalSettingsState {
-    pub fn new() -> GlobalSettingsState {
+    pub fn new(layout_names: Vec<String>) -> GlobalSettingsState {
+        let current_layout = layout_names.first().cloned().unwrap_or_default();
+        let emulated_layouts = std::iter::once(EmulatedLayout(None))
+            .chain(layout_names.iter().cloned().map(Some).map(EmulatedLayout))
+            .collect();
+
         GlobalSettingsState {
             current_theme: Theme::default(),
             theme_pick_list: pick_list::State::default(),
+
+            current_generator: GeneratorKind::default(),
+            generator_pick_list: pick_list::State::default(),
+
+            layout_names,
+            current_layout,
+            layout_pick_list: pick_list::State::default(),
+
+            emulated_layouts,
+            current_emulated_layout: EmulatedLayout(None),
+            emulated_layout_pick_list: pick_list::State::default(),
         }
     }
 
@@ -46,6 +82,24 @@ impl GlobalSettingsState {
                     GlobalSettingsMessage::Action(Action::ChangeTheme(t))
                 });
             }
+            GlobalSettingsMessage::GeneratorChanged(kind) => {
+                self.current_generator = kind;
+                return Command::perform(async move { kind }, |kind| {
+                    GlobalSettingsMessage::Action(Action::ChangeGeneratorKind(kind))
+                });
+            }
+            GlobalSettingsMessage::LayoutChanged(name) => {
+                self.current_layout = name.clone();
+                return Command::perform(async move { name }, |name| {
+                    GlobalSettingsMessage::Action(Action::ChangeKeyboardLayout(name))
+                });
+            }
+            GlobalSettingsMessage::EmulatedLayoutChanged(layout) => {
+                self.current_emulated_layout = layout.clone();
+                return Command::perform(async move { layout.0 }, |name| {
+                    GlobalSettingsMessage::Action(Action::ChangeEmulatedLayout(name))
+                });
+            }
             _ => {}
         }
 
@@ -71,10 +125,67 @@ impl GlobalSettingsState {
             .push(theme_label)
             .push(theme_pick_list);
 
+        let generator_label = Text::new("Word Generator:");
+        let generator_pick_list = PickList::new(
+            &mut self.generator_pick_list,
+            &GeneratorKind::ALL_KINDS[..],
+            Some(self.current_generator),
+            GlobalSettingsMessage::GeneratorChanged,
+        )
+        .style(theme);
+
+        let generator_selector = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(generator_label)
+            .push(generator_pick_list);
+
+        let layout_label = Text::new("Keyboard Layout:");
+        let layout_pick_list = PickList::new(
+            &mut self.layout_pick_list,
+            &self.layout_names[..],
+            Some(self.current_layout.clone()),
+            GlobalSettingsMessage::LayoutChanged,
+        )
+        .style(theme);
+
+        let layout_selector = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(layout_label)
+            .push(layout_pick_list);
+
+        let emulated_layout_label = Text::new("Emulate Layout:");
+        let emulated_layout_pick_list = PickList::new(
+            &mut self.emulated_layout_pick_list,
+            &self.emulated_layouts[..],
+            Some(self.current_emulated_layout.clone()),
+            GlobalSettingsMessage::EmulatedLayoutChanged,
+        )
+        .style(theme);
+
+        let emulated_layout_selector = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(emulated_layout_label)
+            .push(emulated_layout_pick_list);
+
         Column::new()
             .spacing(10)
             .push(title)
             .push(theme_selector)
+            .push(generator_selector)
+            .push(layout_selector)
+            .push(emulated_layout_selector)
             .into()
     }
 }
+
+impl Display for EmulatedLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(name) => f.write_str(name),
+            None => f.write_str("Off"),
+        }
+    }
+}
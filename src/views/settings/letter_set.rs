@@ -0,0 +1,206 @@
+use std::fmt::Display;
+
+use iced::{
+    button, pick_list, text_input, Align, Button, Checkbox, Column, Command, Element, PickList,
+    Row, Text, TextInput,
+};
+use typetest_core::word_generators::letter_set::LetterSetOptions;
+use typetest_themes::ApplicationTheme;
+
+use crate::views::{typing_test::DEFAULT_LETTER_SET, Action};
+
+use super::SettingsMessage;
+
+/// Represents a message specific to the letter set settings view.
+#[derive(Clone, Debug)]
+pub enum LetterSetMessage {
+    LettersChanged(String),
+    UseLetters,
+    MinLengthChanged(WordLength),
+    MaxLengthChanged(WordLength),
+    RealWordsToggled(bool),
+    Action(Action),
+}
+
+/// Represents a number of letters in a generated word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WordLength(pub(super) usize);
+
+impl From<LetterSetMessage> for SettingsMessage {
+    #[inline]
+    fn from(message: LetterSetMessage) -> Self {
+        if let LetterSetMessage::Action(a) = message {
+            SettingsMessage::Action(a)
+        } else {
+            SettingsMessage::LetterSet(message)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LetterSetState {
+    letters: String,
+    status: Option<String>,
+    options: LetterSetOptions,
+
+    letters_input: text_input::State,
+    use_letters_button: button::State,
+    min_length_pick_list: pick_list::State<WordLength>,
+    max_length_pick_list: pick_list::State<WordLength>,
+}
+
+impl LetterSetState {
+    pub fn new() -> LetterSetState {
+        LetterSetState {
+            letters: String::from(DEFAULT_LETTER_SET),
+            status: None,
+            options: LetterSetOptions::default(),
+
+            letters_input: text_input::State::new(),
+            use_letters_button: button::State::new(),
+            min_length_pick_list: pick_list::State::default(),
+            max_length_pick_list: pick_list::State::default(),
+        }
+    }
+
+    pub fn update(&mut self, message: LetterSetMessage) -> Command<LetterSetMessage> {
+        match message {
+            LetterSetMessage::LettersChanged(letters) => self.letters = letters,
+            LetterSetMessage::UseLetters => {
+                if self.letters.trim().is_empty() {
+                    self.status = Some(String::from("Enter at least one letter to use"));
+                    return Command::none();
+                }
+
+                self.status = None;
+                let letters = self.letters.clone();
+                return Command::perform(async move { letters }, |letters| {
+                    LetterSetMessage::Action(Action::ChangeLetterSet(letters))
+                });
+            }
+            LetterSetMessage::MinLengthChanged(WordLength(length)) => {
+                self.options.min_length = length;
+                self.options.max_length = self.options.max_length.max(length);
+                return self.options_changed();
+            }
+            LetterSetMessage::MaxLengthChanged(WordLength(length)) => {
+                self.options.max_length = length;
+                self.options.min_length = self.options.min_length.min(length);
+                return self.options_changed();
+            }
+            LetterSetMessage::RealWordsToggled(real_words) => {
+                self.options.real_words = real_words;
+                return self.options_changed();
+            }
+            _ => {}
+        }
+
+        Command::none()
+    }
+
+    /// Signals that the letter set options have changed.
+    fn options_changed(&self) -> Command<LetterSetMessage> {
+        let options = self.options;
+        Command::perform(async move { options }, |options| {
+            LetterSetMessage::Action(Action::ChangeLetterSetOptions(options))
+        })
+    }
+
+    /// Builds the widget for letter set settings.
+    pub fn view(&mut self, theme: &Box<dyn ApplicationTheme>) -> Element<LetterSetMessage> {
+        const LENGTH_OPTIONS: [WordLength; 10] = [
+            WordLength(1),
+            WordLength(2),
+            WordLength(3),
+            WordLength(4),
+            WordLength(5),
+            WordLength(6),
+            WordLength(7),
+            WordLength(8),
+            WordLength(9),
+            WordLength(10),
+        ];
+
+        let title = Text::new("Letter Set Settings").size(28);
+
+        let letters_input = TextInput::new(
+            &mut self.letters_input,
+            "Letters to practise...",
+            &self.letters,
+            LetterSetMessage::LettersChanged,
+        )
+        .padding(5)
+        .style(theme)
+        .on_submit(LetterSetMessage::UseLetters);
+
+        let use_letters_button =
+            Button::new(&mut self.use_letters_button, Text::new("Use Letters"))
+                .style(theme)
+                .on_press(LetterSetMessage::UseLetters);
+
+        let letters = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(letters_input)
+            .push(use_letters_button);
+
+        let min_length_label = Text::new("Shortest Word:");
+        let min_length_pick_list = PickList::new(
+            &mut self.min_length_pick_list,
+            &LENGTH_OPTIONS[..],
+            Some(WordLength(self.options.min_length)),
+            LetterSetMessage::MinLengthChanged,
+        )
+        .style(theme);
+
+        let min_length = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(min_length_label)
+            .push(min_length_pick_list);
+
+        let max_length_label = Text::new("Longest Word:");
+        let max_length_pick_list = PickList::new(
+            &mut self.max_length_pick_list,
+            &LENGTH_OPTIONS[..],
+            Some(WordLength(self.options.max_length)),
+            LetterSetMessage::MaxLengthChanged,
+        )
+        .style(theme);
+
+        let max_length = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(max_length_label)
+            .push(max_length_pick_list);
+
+        let real_words = Checkbox::new(
+            self.options.real_words,
+            "Include Real Words",
+            LetterSetMessage::RealWordsToggled,
+        )
+        .style(theme);
+
+        let mut content = Column::new().spacing(10).push(title).push(letters);
+
+        if let Some(status) = &self.status {
+            content = content.push(Text::new(status.as_str()));
+        }
+
+        content
+            .push(min_length)
+            .push(max_length)
+            .push(real_words)
+            .into()
+    }
+}
+
+impl Display for WordLength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 == 1 {
+            f.write_str("1 Letter")
+        } else {
+            write!(f, "{} Letters", self.0)
+        }
+    }
+}
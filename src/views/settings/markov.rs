@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use iced::{button, pick_list, Align, Button, Column, Command, Element, PickList, Row, Text};
+use typetest_core::word_generators::markov::{MarkovMode, MarkovModel};
+use typetest_themes::ApplicationTheme;
+
+use crate::{storage, views::Action};
+
+use super::SettingsMessage;
+
+/// Represents a message specific to the Markov generator settings view.
+#[derive(Clone, Debug)]
+pub enum MarkovMessage {
+    OpenCorpus,
+    CorpusTrained(Option<Result<(String, Arc<MarkovModel>), String>>),
+    ModeChanged(MarkovMode),
+    Action(Action),
+}
+
+impl From<MarkovMessage> for SettingsMessage {
+    #[inline]
+    fn from(message: MarkovMessage) -> Self {
+        if let MarkovMessage::Action(a) = message {
+            SettingsMessage::Action(a)
+        } else {
+            SettingsMessage::Markov(message)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MarkovState {
+    corpus: String,
+    status: Option<String>,
+    training: bool,
+    mode: MarkovMode,
+
+    open_corpus_button: button::State,
+    mode_pick_list: pick_list::State<MarkovMode>,
+}
+
+impl MarkovState {
+    pub fn new() -> MarkovState {
+        MarkovState {
+            corpus: String::from("Built-in Passages"),
+            status: None,
+            training: false,
+            mode: MarkovMode::default(),
+
+            open_corpus_button: button::State::new(),
+            mode_pick_list: pick_list::State::default(),
+        }
+    }
+
+    pub fn update(&mut self, message: MarkovMessage) -> Command<MarkovMessage> {
+        match message {
+            MarkovMessage::OpenCorpus => {
+                self.training = true;
+                self.status = None;
+                return Command::perform(train_on_corpus(), MarkovMessage::CorpusTrained);
+            }
+            MarkovMessage::CorpusTrained(Some(Ok((corpus, model)))) => {
+                self.training = false;
+                self.corpus = corpus;
+                self.status = if model.is_empty() {
+                    Some(String::from("The corpus has no words to train on"))
+                } else {
+                    None
+                };
+
+                return Command::perform(async move { model }, |model| {
+                    MarkovMessage::Action(Action::ChangeMarkovModel(model))
+                });
+            }
+            MarkovMessage::CorpusTrained(Some(Err(e))) => {
+                self.training = false;
+                self.status = Some(format!("Failed to open corpus: {}", e));
+            }
+            MarkovMessage::CorpusTrained(None) => self.training = false,
+            MarkovMessage::ModeChanged(mode) => {
+                self.mode = mode;
+                return Command::perform(async move { mode }, |mode| {
+                    MarkovMessage::Action(Action::ChangeMarkovMode(mode))
+                });
+            }
+            _ => {}
+        }
+
+        Command::none()
+    }
+
+    /// Notes that the model cached from a previous run is being used.
+    pub fn use_cached_model(&mut self) {
+        self.corpus = String::from("Previous Corpus");
+    }
+
+    /// Builds the widget for Markov generator settings.
+    pub fn view(&mut self, theme: &Box<dyn ApplicationTheme>) -> Element<MarkovMessage> {
+        let title = Text::new("Markov Chain Settings").size(28);
+
+        let corpus_label = Text::new(format!("Corpus: {}", self.corpus));
+        let open_corpus_button = {
+            let tmp = Button::new(
+                &mut self.open_corpus_button,
+                Text::new(if self.training {
+                    "Training..."
+                } else {
+                    "Open Corpus..."
+                }),
+            )
+            .style(theme);
+
+            if self.training {
+                tmp
+            } else {
+                tmp.on_press(MarkovMessage::OpenCorpus)
+            }
+        };
+
+        let corpus = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(corpus_label)
+            .push(open_corpus_button);
+
+        let mode_label = Text::new("Generate:");
+        let mode_pick_list = PickList::new(
+            &mut self.mode_pick_list,
+            &MarkovMode::ALL_MODES[..],
+            Some(self.mode),
+            MarkovMessage::ModeChanged,
+        )
+        .style(theme);
+
+        let mode = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(mode_label)
+            .push(mode_pick_list);
+
+        let mut content = Column::new().spacing(10).push(title).push(corpus);
+
+        if let Some(status) = &self.status {
+            content = content.push(Text::new(status.as_str()));
+        }
+
+        content.push(mode).into()
+    }
+}
+
+/// Prompts the user to pick a text file, then trains a Markov model on it.
+///
+/// Returns the name of the file along with the model, or `None` if no file was picked.
+async fn train_on_corpus() -> Option<Result<(String, Arc<MarkovModel>), String>> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("Text Files", &["txt"])
+        .pick_file()
+        .await?;
+
+    let corpus = match std::fs::read_to_string(file.path()) {
+        Ok(corpus) => corpus,
+        Err(e) => return Some(Err(e.to_string())),
+    };
+
+    Some(Ok((
+        file.file_name(),
+        Arc::new(storage::train_markov_model(&corpus)),
+    )))
+}
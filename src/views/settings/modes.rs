@@ -0,0 +1,249 @@
+use std::fmt::Display;
+
+use iced::{pick_list, Align, Checkbox, Column, Command, Element, PickList, Row, Text};
+use typetest_core::modes::{ConfidenceMode, FailConditions, StopOnError};
+use typetest_themes::ApplicationTheme;
+
+use crate::views::Action;
+
+use super::SettingsMessage;
+
+/// Represents a message specific to the test mode settings view.
+#[derive(Clone, Debug)]
+pub enum ModesMessage {
+    StopOnErrorChanged(StopOnError),
+    ConfidenceModeChanged(ConfidenceMode),
+    SuddenDeathToggled(bool),
+    MinAccuracyChanged(MinAccuracy),
+    MinWpmChanged(MinWpm),
+    WpmGracePeriodChanged(u64),
+    Action(Action),
+}
+
+/// Represents an optional minimum accuracy, as a percentage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinAccuracy(Option<u64>);
+
+/// Represents an optional minimum WPM.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinWpm(Option<u64>);
+
+impl From<ModesMessage> for SettingsMessage {
+    #[inline]
+    fn from(message: ModesMessage) -> Self {
+        if let ModesMessage::Action(a) = message {
+            SettingsMessage::Action(a)
+        } else {
+            SettingsMessage::Modes(message)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ModesState {
+    stop_on_error: StopOnError,
+    stop_on_error_pick_list: pick_list::State<StopOnError>,
+
+    confidence_mode: ConfidenceMode,
+    confidence_mode_pick_list: pick_list::State<ConfidenceMode>,
+
+    fail_conditions: FailConditions,
+    min_accuracy_pick_list: pick_list::State<MinAccuracy>,
+    min_wpm_pick_list: pick_list::State<MinWpm>,
+    wpm_grace_period_pick_list: pick_list::State<u64>,
+}
+
+impl ModesState {
+    pub fn new() -> ModesState {
+        ModesState {
+            stop_on_error: StopOnError::default(),
+            stop_on_error_pick_list: pick_list::State::default(),
+
+            confidence_mode: ConfidenceMode::default(),
+            confidence_mode_pick_list: pick_list::State::default(),
+
+            fail_conditions: FailConditions::default(),
+            min_accuracy_pick_list: pick_list::State::default(),
+            min_wpm_pick_list: pick_list::State::default(),
+            wpm_grace_period_pick_list: pick_list::State::default(),
+        }
+    }
+
+    pub fn update(&mut self, message: ModesMessage) -> Command<ModesMessage> {
+        match message {
+            ModesMessage::StopOnErrorChanged(mode) => {
+                self.stop_on_error = mode;
+                Command::perform(async move { mode }, |mode| {
+                    ModesMessage::Action(Action::ChangeStopOnError(mode))
+                })
+            }
+            ModesMessage::ConfidenceModeChanged(mode) => {
+                self.confidence_mode = mode;
+                Command::perform(async move { mode }, |mode| {
+                    ModesMessage::Action(Action::ChangeConfidenceMode(mode))
+                })
+            }
+            ModesMessage::SuddenDeathToggled(sudden_death) => {
+                self.fail_conditions.sudden_death = sudden_death;
+                self.fail_conditions_changed()
+            }
+            ModesMessage::MinAccuracyChanged(MinAccuracy(accuracy)) => {
+                self.fail_conditions.min_accuracy = accuracy;
+                self.fail_conditions_changed()
+            }
+            ModesMessage::MinWpmChanged(MinWpm(wpm)) => {
+                self.fail_conditions.min_wpm = wpm;
+                self.fail_conditions_changed()
+            }
+            ModesMessage::WpmGracePeriodChanged(seconds) => {
+                self.fail_conditions.wpm_grace_seconds = seconds;
+                self.fail_conditions_changed()
+            }
+            _ => Command::none(),
+        }
+    }
+
+    /// Signals that the fail conditions have changed.
+    fn fail_conditions_changed(&self) -> Command<ModesMessage> {
+        let conditions = self.fail_conditions;
+        Command::perform(async move { conditions }, |conditions| {
+            ModesMessage::Action(Action::ChangeFailConditions(conditions))
+        })
+    }
+
+    /// Builds the widget for test mode settings.
+    pub fn view(&mut self, theme: &Box<dyn ApplicationTheme>) -> Element<ModesMessage> {
+        const MIN_ACCURACY_OPTIONS: [MinAccuracy; 6] = [
+            MinAccuracy(None),
+            MinAccuracy(Some(80)),
+            MinAccuracy(Some(90)),
+            MinAccuracy(Some(95)),
+            MinAccuracy(Some(98)),
+            MinAccuracy(Some(100)),
+        ];
+
+        const MIN_WPM_OPTIONS: [MinWpm; 6] = [
+            MinWpm(None),
+            MinWpm(Some(20)),
+            MinWpm(Some(40)),
+            MinWpm(Some(60)),
+            MinWpm(Some(80)),
+            MinWpm(Some(100)),
+        ];
+
+        const GRACE_PERIOD_OPTIONS: [u64; 4] = [5, 10, 15, 30];
+
+        let title = Text::new("Test Modes").size(28);
+
+        let stop_on_error_label = Text::new("Stop on Error:");
+        let stop_on_error_pick_list = PickList::new(
+            &mut self.stop_on_error_pick_list,
+            &StopOnError::ALL_MODES[..],
+            Some(self.stop_on_error),
+            ModesMessage::StopOnErrorChanged,
+        )
+        .style(theme);
+
+        let stop_on_error = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(stop_on_error_label)
+            .push(stop_on_error_pick_list);
+
+        let confidence_mode_label = Text::new("Confidence Mode:");
+        let confidence_mode_pick_list = PickList::new(
+            &mut self.confidence_mode_pick_list,
+            &ConfidenceMode::ALL_MODES[..],
+            Some(self.confidence_mode),
+            ModesMessage::ConfidenceModeChanged,
+        )
+        .style(theme);
+
+        let confidence_mode = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(confidence_mode_label)
+            .push(confidence_mode_pick_list);
+
+        let sudden_death = Checkbox::new(
+            self.fail_conditions.sudden_death,
+            "Fail on First Incorrect Word",
+            ModesMessage::SuddenDeathToggled,
+        )
+        .style(theme);
+
+        let min_accuracy_label = Text::new("Minimum Accuracy:");
+        let min_accuracy_pick_list = PickList::new(
+            &mut self.min_accuracy_pick_list,
+            &MIN_ACCURACY_OPTIONS[..],
+            Some(MinAccuracy(self.fail_conditions.min_accuracy)),
+            ModesMessage::MinAccuracyChanged,
+        )
+        .style(theme);
+
+        let min_accuracy = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(min_accuracy_label)
+            .push(min_accuracy_pick_list);
+
+        let min_wpm_label = Text::new("Minimum WPM:");
+        let min_wpm_pick_list = PickList::new(
+            &mut self.min_wpm_pick_list,
+            &MIN_WPM_OPTIONS[..],
+            Some(MinWpm(self.fail_conditions.min_wpm)),
+            ModesMessage::MinWpmChanged,
+        )
+        .style(theme);
+
+        let min_wpm = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(min_wpm_label)
+            .push(min_wpm_pick_list);
+
+        let grace_period_label = Text::new("Minimum WPM Grace Period (s):");
+        let grace_period_pick_list = PickList::new(
+            &mut self.wpm_grace_period_pick_list,
+            &GRACE_PERIOD_OPTIONS[..],
+            Some(self.fail_conditions.wpm_grace_seconds),
+            ModesMessage::WpmGracePeriodChanged,
+        )
+        .style(theme);
+
+        let grace_period = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(grace_period_label)
+            .push(grace_period_pick_list);
+
+        Column::new()
+            .spacing(10)
+            .push(title)
+            .push(stop_on_error)
+            .push(confidence_mode)
+            .push(sudden_death)
+            .push(min_accuracy)
+            .push(min_wpm)
+            .push(grace_period)
+            .into()
+    }
+}
+
+impl Display for MinAccuracy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(accuracy) => write!(f, "{}%", accuracy),
+            None => f.write_str("Off"),
+        }
+    }
+}
+
+impl Display for MinWpm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(wpm) => write!(f, "{} WPM", wpm),
+            None => f.write_str("Off"),
+        }
+    }
+}
@@ -1,17 +1,40 @@
-use iced::{pick_list, Align, Column, Command, Element, PickList, Row, Text};
+use std::fmt::Display;
+
+use iced::{
+    button, pick_list, text_input, Align, Button, Column, Command, Element, PickList, Row, Text,
+    TextInput,
+};
+use typetest_core::word_generators::random::RandomWordOptions;
 use typetest_themes::ApplicationTheme;
 
 use crate::views::Action;
 
-use super::SettingsMessage;
+use super::{letter_set::WordLength, SettingsMessage};
 
 /// Represents a message specific to the random generator settings view.
 #[derive(Clone, Debug)]
 pub enum RandomGeneratorMessage {
-    TimeLengthChanged(u64),
+    TimeLengthChanged(TestLength),
+    MinLengthChanged(WordLength),
+    MaxLengthChanged(MaxWordLength),
+    RepeatWindowChanged(RepeatWindow),
+    BlocklistChanged(String),
+    UseBlocklist,
     Action(Action),
 }
 
+/// Represents an optional time limit for a test, in seconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TestLength(Option<u64>);
+
+/// Represents an optional limit on the number of letters in a word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaxWordLength(Option<usize>);
+
+/// Represents the number of previous words that a new word can't repeat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RepeatWindow(usize);
+
 impl From<RandomGeneratorMessage> for SettingsMessage {
     #[inline]
     fn from(message: RandomGeneratorMessage) -> Self {
@@ -25,33 +48,124 @@ impl From<RandomGeneratorMessage> for SettingsMessage {
 
 #[derive(Debug)]
 pub struct RandomGeneratorState {
-    time_length_seconds: u64,
-    time_length_pick_list: pick_list::State<u64>,
+    time_length_seconds: Option<u64>,
+    options: RandomWordOptions,
+    blocklist: String,
+
+    time_length_pick_list: pick_list::State<TestLength>,
+    min_length_pick_list: pick_list::State<WordLength>,
+    max_length_pick_list: pick_list::State<MaxWordLength>,
+    repeat_window_pick_list: pick_list::State<RepeatWindow>,
+    blocklist_input: text_input::State,
+    use_blocklist_button: button::State,
 }
 
 impl RandomGeneratorState {
     pub fn new() -> RandomGeneratorState {
         RandomGeneratorState {
-            time_length_seconds: 60,
+            time_length_seconds: Some(60),
+            options: RandomWordOptions::default(),
+            blocklist: String::new(),
+
             time_length_pick_list: pick_list::State::default(),
+            min_length_pick_list: pick_list::State::default(),
+            max_length_pick_list: pick_list::State::default(),
+            repeat_window_pick_list: pick_list::State::default(),
+            blocklist_input: text_input::State::new(),
+            use_blocklist_button: button::State::new(),
         }
     }
 
     pub fn update(&mut self, message: RandomGeneratorMessage) -> Command<RandomGeneratorMessage> {
         match message {
-            RandomGeneratorMessage::TimeLengthChanged(time) => {
+            RandomGeneratorMessage::TimeLengthChanged(TestLength(time)) => {
                 self.time_length_seconds = time;
-                Command::perform(async move { time }, |time| {
+                return Command::perform(async move { time }, |time| {
                     RandomGeneratorMessage::Action(Action::ChangeTimeLength(time))
-                })
+                });
+            }
+            RandomGeneratorMessage::MinLengthChanged(WordLength(length)) => {
+                self.options.min_length = length;
+                self.options.max_length = self.options.max_length.map(|max| max.max(length));
+                return self.options_changed();
             }
-            _ => Command::none(),
+            RandomGeneratorMessage::MaxLengthChanged(MaxWordLength(length)) => {
+                self.options.max_length = length;
+                if let Some(length) = length {
+                    self.options.min_length = self.options.min_length.min(length);
+                }
+                return self.options_changed();
+            }
+            RandomGeneratorMessage::RepeatWindowChanged(RepeatWindow(window)) => {
+                self.options.repeat_window = window;
+                return self.options_changed();
+            }
+            RandomGeneratorMessage::BlocklistChanged(blocklist) => self.blocklist = blocklist,
+            RandomGeneratorMessage::UseBlocklist => {
+                self.options.blocklist = self
+                    .blocklist
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|word| !word.is_empty())
+                    .map(String::from)
+                    .collect();
+                return self.options_changed();
+            }
+            _ => {}
         }
+
+        Command::none()
+    }
+
+    /// Signals that the random word options have changed.
+    fn options_changed(&self) -> Command<RandomGeneratorMessage> {
+        let options = self.options.clone();
+        Command::perform(async move { options }, |options| {
+            RandomGeneratorMessage::Action(Action::ChangeRandomWordOptions(options))
+        })
     }
 
     /// Builds the widget for random generator settings.
     pub fn view(&mut self, theme: &Box<dyn ApplicationTheme>) -> Element<RandomGeneratorMessage> {
-        const TIME_OPTIONS: [u64; 5] = [10, 30, 60, 120, 300];
+        const TIME_OPTIONS: [TestLength; 6] = [
+            TestLength(Some(10)),
+            TestLength(Some(30)),
+            TestLength(Some(60)),
+            TestLength(Some(120)),
+            TestLength(Some(300)),
+            TestLength(None),
+        ];
+
+        const MIN_LENGTH_OPTIONS: [WordLength; 8] = [
+            WordLength(1),
+            WordLength(2),
+            WordLength(3),
+            WordLength(4),
+            WordLength(5),
+            WordLength(6),
+            WordLength(7),
+            WordLength(8),
+        ];
+
+        const MAX_LENGTH_OPTIONS: [MaxWordLength; 9] = [
+            MaxWordLength(Some(3)),
+            MaxWordLength(Some(4)),
+            MaxWordLength(Some(5)),
+            MaxWordLength(Some(6)),
+            MaxWordLength(Some(7)),
+            MaxWordLength(Some(8)),
+            MaxWordLength(Some(10)),
+            MaxWordLength(Some(12)),
+            MaxWordLength(None),
+        ];
+
+        const REPEAT_WINDOW_OPTIONS: [RepeatWindow; 6] = [
+            RepeatWindow(0),
+            RepeatWindow(1),
+            RepeatWindow(3),
+            RepeatWindow(5),
+            RepeatWindow(10),
+            RepeatWindow(20),
+        ];
 
         let title = Text::new("Random Generator Settings").size(28);
 
@@ -59,7 +173,7 @@ impl RandomGeneratorState {
         let time_length_pick_list = PickList::new(
             &mut self.time_length_pick_list,
             &TIME_OPTIONS[..],
-            Some(self.time_length_seconds),
+            Some(TestLength(self.time_length_seconds)),
             RandomGeneratorMessage::TimeLengthChanged,
         )
         .style(theme);
@@ -70,10 +184,108 @@ impl RandomGeneratorState {
             .push(time_length_label)
             .push(time_length_pick_list);
 
+        let min_length_label = Text::new("Shortest Word:");
+        let min_length_pick_list = PickList::new(
+            &mut self.min_length_pick_list,
+            &MIN_LENGTH_OPTIONS[..],
+            Some(WordLength(self.options.min_length)),
+            RandomGeneratorMessage::MinLengthChanged,
+        )
+        .style(theme);
+
+        let min_length = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(min_length_label)
+            .push(min_length_pick_list);
+
+        let max_length_label = Text::new("Longest Word:");
+        let max_length_pick_list = PickList::new(
+            &mut self.max_length_pick_list,
+            &MAX_LENGTH_OPTIONS[..],
+            Some(MaxWordLength(self.options.max_length)),
+            RandomGeneratorMessage::MaxLengthChanged,
+        )
+        .style(theme);
+
+        let max_length = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(max_length_label)
+            .push(max_length_pick_list);
+
+        let repeat_window_label = Text::new("Avoid Repeating:");
+        let repeat_window_pick_list = PickList::new(
+            &mut self.repeat_window_pick_list,
+            &REPEAT_WINDOW_OPTIONS[..],
+            Some(RepeatWindow(self.options.repeat_window)),
+            RandomGeneratorMessage::RepeatWindowChanged,
+        )
+        .style(theme);
+
+        let repeat_window = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(repeat_window_label)
+            .push(repeat_window_pick_list);
+
+        let blocklist_input = TextInput::new(
+            &mut self.blocklist_input,
+            "Words to exclude...",
+            &self.blocklist,
+            RandomGeneratorMessage::BlocklistChanged,
+        )
+        .padding(5)
+        .style(theme)
+        .on_submit(RandomGeneratorMessage::UseBlocklist);
+
+        let use_blocklist_button =
+            Button::new(&mut self.use_blocklist_button, Text::new("Exclude"))
+                .style(theme)
+                .on_press(RandomGeneratorMessage::UseBlocklist);
+
+        let blocklist = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(blocklist_input)
+            .push(use_blocklist_button);
+
         Column::new()
             .spacing(10)
             .push(title)
             .push(time_length)
+            .push(min_length)
+            .push(max_length)
+            .push(repeat_window)
+            .push(blocklist)
             .into()
     }
 }
+
+impl Display for TestLength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(seconds) => write!(f, "{}", seconds),
+            None => f.write_str("Untimed (Zen)"),
+        }
+    }
+}
+
+impl Display for MaxWordLength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(length) => write!(f, "{} Letters", length),
+            None => f.write_str("Any Length"),
+        }
+    }
+}
+
+impl Display for RepeatWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => f.write_str("Off"),
+            1 => f.write_str("Previous Word"),
+            words => write!(f, "Previous {} Words", words),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use iced::{pick_list, Align, Column, Command, Element, PickList, Row, Text};
+use typetest_core::stats::scoring::{AccuracyMethod, Scoring, WpmMethod};
+use typetest_themes::ApplicationTheme;
+
+use crate::views::Action;
+
+use super::SettingsMessage;
+
+/// Represents a message specific to the scoring settings view.
+#[derive(Clone, Debug)]
+pub enum ScoringMessage {
+    WpmMethodChanged(WpmMethod),
+    AccuracyMethodChanged(AccuracyMethod),
+    Action(Action),
+}
+
+impl From<ScoringMessage> for SettingsMessage {
+    #[inline]
+    fn from(message: ScoringMessage) -> Self {
+        if let ScoringMessage::Action(a) = message {
+            SettingsMessage::Action(a)
+        } else {
+            SettingsMessage::Scoring(message)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ScoringState {
+    scoring: Scoring,
+    wpm_method_pick_list: pick_list::State<WpmMethod>,
+    accuracy_method_pick_list: pick_list::State<AccuracyMethod>,
+}
+
+impl ScoringState {
+    pub fn new() -> ScoringState {
+        ScoringState {
+            scoring: Scoring::default(),
+            wpm_method_pick_list: pick_list::State::default(),
+            accuracy_method_pick_list: pick_list::State::default(),
+        }
+    }
+
+    pub fn update(&mut self, message: ScoringMessage) -> Command<ScoringMessage> {
+        match message {
+            ScoringMessage::WpmMethodChanged(method) => self.scoring.wpm = method,
+            ScoringMessage::AccuracyMethodChanged(method) => self.scoring.accuracy = method,
+            _ => return Command::none(),
+        }
+
+        let scoring = self.scoring;
+        Command::perform(async move { scoring }, |scoring| {
+            ScoringMessage::Action(Action::ChangeScoring(scoring))
+        })
+    }
+
+    /// Builds the widget for scoring settings.
+    pub fn view(&mut self, theme: &Box<dyn ApplicationTheme>) -> Element<ScoringMessage> {
+        let title = Text::new("Scoring Settings").size(28);
+
+        let wpm_method_label = Text::new("WPM Calculation:");
+        let wpm_method_pick_list = PickList::new(
+            &mut self.wpm_method_pick_list,
+            &WpmMethod::ALL_METHODS[..],
+            Some(self.scoring.wpm),
+            ScoringMessage::WpmMethodChanged,
+        )
+        .style(theme);
+
+        let wpm_method = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(wpm_method_label)
+            .push(wpm_method_pick_list);
+
+        let accuracy_method_label = Text::new("Accuracy Calculation:");
+        let accuracy_method_pick_list = PickList::new(
+            &mut self.accuracy_method_pick_list,
+            &AccuracyMethod::ALL_METHODS[..],
+            Some(self.scoring.accuracy),
+            ScoringMessage::AccuracyMethodChanged,
+        )
+        .style(theme);
+
+        let accuracy_method = Row::new()
+            .align_items(Align::Center)
+            .spacing(10)
+            .push(accuracy_method_label)
+            .push(accuracy_method_pick_list);
+
+        Column::new()
+            .spacing(10)
+            .push(title)
+            .push(wpm_method)
+            .push(accuracy_method)
+            .into()
+    }
+}
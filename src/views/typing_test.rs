@@ -1,27 +1,76 @@
-use std::time::{Duration, Instant};
+use std::{
+    fmt::Display,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use iced::{
     button, text_input, Align, Button, Column, Command, Container, Element, HorizontalAlignment,
     Row, Text, TextInput,
 };
 use typetest_core::{
-    stats::TestStats,
-    word_generators::{random::InfiniteWordGenerator, DisplayedWord, WordGenerator, WordStatus},
+    history::TestResult,
+    layouts::LayoutEmulation,
+    lessons::{Lesson, LessonId},
+    line_layout::LineLayout,
+    modes::{ConfidenceMode, FailConditions, FailReason, StopOnError},
+    stats::{scoring::Scoring, MissedWord, TestStats},
+    word_generators::{
+        code::{CodeLanguage, CodeWordGenerator},
+        custom::{CustomTextOptions, CustomWordGenerator},
+        drill::DrillWordGenerator,
+        letter_set::{LetterSetOptions, LetterSetWordGenerator},
+        markov::{MarkovMode, MarkovModel, MarkovWordGenerator},
+        passage::PassageWordGenerator,
+        random::{InfiniteWordGenerator, RandomWordOptions},
+        DisplayedWord, Separator, WordGenerator, WordStatus, TAB_WIDTH,
+    },
 };
 use typetest_themes::{ApplicationTheme, Theme};
 
-use crate::widgets::word_submission::SubmissionWrapper;
+use crate::widgets::{
+    measured_lines::{LineMetrics, MeasuredLines},
+    word_submission::SubmissionWrapper,
+};
+
+use super::{format_time_mm_ss, Action, View};
+
+/// The maximum number of characters shown on each line of words, when there is space for them.
+pub const MAX_CHARS: usize = 80;
+
+/// The text size used to display words.
+pub const WORD_TEXT_SIZE: u16 = 22;
+
+/// The letters used by the letter set generator until they are changed in the settings.
+pub const DEFAULT_LETTER_SET: &str = "asdfghjkl";
 
-use super::{Action, View};
+/// The number of times each missed word is repeated in a practice drill.
+const PRACTICE_REPETITIONS: usize = 3;
 
-const MAX_CHARS: usize = 80;
+/// The marker displayed after words that are submitted with Enter.
+const NEWLINE_MARKER: char = '¶';
 
 /// Represents the state for the typing test view.
 pub struct TypingTestState {
     word_gen: Box<dyn WordGenerator>,
+    generator_kind: GeneratorKind,
+    /// Whether the current word generator is a practice drill, rather than one built from the
+    /// settings.
+    practising: bool,
+    /// The lesson that the current word generator was built for, if any.
+    lesson: Option<LessonId>,
+    generator_settings: GeneratorSettings,
+    stop_on_error: StopOnError,
+    confidence_mode: ConfidenceMode,
+    fail_conditions: FailConditions,
+    layout_emulation: Option<LayoutEmulation>,
+    scoring: Scoring,
     stats: TestStats,
     status: TypingTestStatus,
 
+    /// Lays out the generated words into lines, keeping any words that haven't been shown yet.
+    line_layout: LineLayout,
+    line_metrics: LineMetrics,
     current_pos: usize,
     current_line: Vec<DisplayedWord>,
     next_line: Vec<DisplayedWord>,
@@ -29,8 +78,9 @@ pub struct TypingTestState {
     current_input: String,
 
     test_start: Instant,
-    test_length_seconds: u64,
-    remaining_seconds: u64,
+    test_length_seconds: Option<u64>,
+    /// The remaining time for timed tests, or the elapsed time for untimed tests.
+    timer_seconds: u64,
 
     show_wpm: bool,
     show_timer: bool,
@@ -40,6 +90,8 @@ pub struct TypingTestState {
     timer_button: button::State,
     redo_button: button::State,
     settings_button: button::State,
+    history_button: button::State,
+    lessons_button: button::State,
 }
 
 /// Represents the messages used by the typing test view.
@@ -47,11 +99,14 @@ pub struct TypingTestState {
 pub enum TypingTestMessage {
     TimerTick(Instant),
     InputChanged(String),
-    WordSubmitted,
+    WordSubmitted(Separator),
+    Indent,
+    EndTest,
 
     ToggleWPM,
     ToggleTimer,
     Redo,
+    LinesMeasured(LineMetrics),
 
     Action(Action),
 }
@@ -62,42 +117,81 @@ pub enum TypingTestStatus {
     NotStarted,
     Started,
     Finished,
+    Failed,
+}
+
+/// Represents the different kinds of word generator that can be used for a typing test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeneratorKind {
+    RandomWords,
+    Passages,
+    Code,
+    Custom,
+    LetterSet,
+    Markov,
+}
+
+/// Represents the settings used to build each kind of word generator.
+struct GeneratorSettings {
+    random_word_options: RandomWordOptions,
+    code_language: CodeLanguage,
+    custom_text: String,
+    custom_text_options: CustomTextOptions,
+    letter_set: String,
+    letter_set_options: LetterSetOptions,
+    markov_model: Arc<MarkovModel>,
+    markov_mode: MarkovMode,
 }
 
 impl TypingTestState {
     pub fn new() -> Self {
-        let mut word_gen = Box::new(InfiniteWordGenerator::default());
-        let mut current_line = Vec::new();
-        let mut next_line = Vec::new();
+        let generator_kind = GeneratorKind::default();
+        let generator_settings = GeneratorSettings::default();
 
-        word_gen.fill_line(&mut current_line, MAX_CHARS);
-        word_gen.fill_line(&mut next_line, MAX_CHARS);
+        let word_gen = generator_kind.build(&generator_settings);
 
-        Self {
+        let mut state = Self {
             word_gen,
-            current_line,
-            next_line,
+            generator_kind,
+            practising: false,
+            lesson: None,
+            generator_settings,
+            stop_on_error: StopOnError::default(),
+            confidence_mode: ConfidenceMode::default(),
+            fail_conditions: FailConditions::default(),
+            layout_emulation: None,
+            scoring: Scoring::default(),
+            current_line: Vec::new(),
+            next_line: Vec::new(),
 
             stats: TestStats::new(),
             status: TypingTestStatus::NotStarted,
 
+            line_layout: LineLayout::new(),
+            line_metrics: LineMetrics::columns(MAX_CHARS),
             current_pos: 0,
 
             current_input: String::new(),
 
             test_start: Instant::now(),
-            test_length_seconds: 60,
-            remaining_seconds: 60,
+            test_length_seconds: Some(60),
+            timer_seconds: 60,
 
             show_wpm: true,
             show_timer: true,
 
             input_box: text_input::State::new(),
             settings_button: button::State::new(),
+            history_button: button::State::new(),
+            lessons_button: button::State::new(),
             wpm_button: button::State::new(),
             timer_button: button::State::new(),
             redo_button: button::State::new(),
-        }
+        };
+
+        state.fill_line(false);
+        state.fill_line(true);
+        state
     }
 
     pub fn update(&mut self, message: TypingTestMessage) -> iced::Command<TypingTestMessage> {
@@ -112,22 +206,53 @@ impl TypingTestState {
                     .unwrap_or_default()
                     .as_secs();
 
-                let new_remaining = self.test_length_seconds - elapsed;
-                if self.remaining_seconds != new_remaining {
+                // Untimed tests count up instead of down
+                let new_timer = match self.test_length_seconds {
+                    Some(length) => length.saturating_sub(elapsed),
+                    None => elapsed,
+                };
+
+                if self.timer_seconds != new_timer {
                     self.stats.checkpoint();
-                    self.remaining_seconds = new_remaining;
+                    self.timer_seconds = new_timer;
+
+                    let fail_reason = self
+                        .stats
+                        .get_latest_checkpoint()
+                        .and_then(|checkpoint| self.fail_conditions.check_checkpoint(checkpoint));
+
+                    if let Some(reason) = fail_reason {
+                        return self.fail_test(reason);
+                    }
                 }
 
-                if self.remaining_seconds == 0 {
+                if self.test_length_seconds.is_some() && self.timer_seconds == 0 {
                     self.status = TypingTestStatus::Finished;
-                    let stats = self.stats.clone();
-                    return Command::perform(async move { stats }, |stats| {
-                        TypingTestMessage::Action(Action::DisplayResults(stats))
-                    });
+                    return self.display_results();
                 }
             }
+            TypingTestMessage::EndTest => {
+                if self.status != TypingTestStatus::Started || self.test_length_seconds.is_some() {
+                    return Command::none();
+                }
+
+                self.status = TypingTestStatus::Finished;
+                return self.display_results();
+            }
             TypingTestMessage::InputChanged(s) => {
-                if self.status == TypingTestStatus::Finished {
+                if let TypingTestStatus::Finished | TypingTestStatus::Failed = self.status {
+                    return Command::none();
+                }
+
+                // There's nothing to type if a custom text is empty
+                if self.current_line.is_empty() {
+                    return Command::none();
+                }
+
+                // Edits that would correct the input are discarded at max confidence, including
+                // typing over a selection or cutting it
+                if !self.confidence_mode.allows_edit(&self.current_input, &s) {
+                    self.input_box.move_cursor_to_end();
                     return Command::none();
                 }
 
@@ -138,24 +263,57 @@ impl TypingTestState {
                     self.test_start = Instant::now();
                 }
 
+                self.stats.record_input(
+                    &self.current_line[self.current_pos].word,
+                    &self.current_input,
+                    &s,
+                );
+
+                // Incorrect letters are discarded when stopping on letters
+                let is_prefix = self.current_line[self.current_pos].word.starts_with(&s);
+                if !is_prefix && self.stop_on_error == StopOnError::Letter {
+                    self.stats.block_attempt();
+
+                    // Record the input being reverted, so that replays match what was displayed
+                    self.stats.record_input(
+                        &self.current_line[self.current_pos].word,
+                        &s,
+                        &self.current_input,
+                    );
+                    return Command::none();
+                }
+
                 // Update the status for the current word
-                self.current_line[self.current_pos].status =
-                    if self.current_line[self.current_pos].word.starts_with(&s) {
-                        WordStatus::NotTyped
-                    } else {
-                        WordStatus::Incorrect
-                    };
+                self.current_line[self.current_pos].status = if is_prefix {
+                    WordStatus::NotTyped
+                } else {
+                    WordStatus::Incorrect
+                };
 
                 self.current_input = s;
             }
-            TypingTestMessage::WordSubmitted => {
+            TypingTestMessage::WordSubmitted(separator) => {
                 if self.status != TypingTestStatus::Started || self.current_input.is_empty() {
                     return Command::none();
                 }
 
-                let is_correct = self.stats.submit_word(
-                    &self.current_line[self.current_pos].word,
+                let current_word = &self.current_line[self.current_pos];
+
+                // Incorrect words can't be submitted in any of the stop on error modes
+                if self.stop_on_error != StopOnError::Off
+                    && (current_word.word != self.current_input
+                        || current_word.separator != separator)
+                {
+                    self.stats.block_attempt();
+                    self.current_line[self.current_pos].status = WordStatus::Incorrect;
+                    return Command::none();
+                }
+
+                let is_correct = self.stats.submit_word_with_separator(
+                    &current_word.word,
                     &self.current_input,
+                    current_word.separator,
+                    separator,
                 );
 
                 self.current_input.clear();
@@ -165,17 +323,35 @@ impl TypingTestState {
                     WordStatus::Incorrect
                 };
 
+                if let Some(reason) = self.fail_conditions.check_word(is_correct) {
+                    return self.fail_test(reason);
+                }
+
                 if self.current_pos >= self.current_line.len() - 1 {
                     self.current_pos = 0;
                     std::mem::swap(&mut self.current_line, &mut self.next_line);
-                    self.word_gen.fill_line(&mut self.next_line, MAX_CHARS);
+                    self.fill_line(true);
+
+                    // Custom texts that don't repeat end the test once they have been typed
+                    if self.current_line.is_empty() {
+                        self.status = TypingTestStatus::Finished;
+                        return self.display_results();
+                    }
                 } else {
                     self.current_pos += 1;
                 }
             }
+            TypingTestMessage::Indent => {
+                let mut input = self.current_input.clone();
+                input.push('\t');
+
+                self.input_box.move_cursor_to_end();
+                return self.update(TypingTestMessage::InputChanged(input));
+            }
             TypingTestMessage::ToggleWPM => self.show_wpm = !self.show_wpm,
             TypingTestMessage::ToggleTimer => self.show_timer = !self.show_timer,
             TypingTestMessage::Redo => self.reset_test_state(true),
+            TypingTestMessage::LinesMeasured(metrics) => self.reflow_lines(metrics),
             _ => {}
         }
 
@@ -186,15 +362,21 @@ impl TypingTestState {
         &'a mut self,
         theme: &'a Box<dyn ApplicationTheme>,
     ) -> iced::Element<'a, TypingTestMessage> {
+        let expects_newline = self.expects_newline();
+        let expects_indent = self.expects_indent();
+
         // Typing Area
-        // NOTE: +1 to max chars due to tiny gap between displayed words
         let current_line = line_of_displayed_words(&self.current_line, self.current_pos, theme);
         let next_line = line_of_displayed_words(&self.next_line, self.next_line.len(), theme);
-        let line_display = Column::new()
-            .spacing(5)
-            .push(blank_line())
-            .push(current_line)
-            .push(next_line);
+        let line_display = measured_lines(
+            Column::new()
+                .spacing(5)
+                .push(blank_line())
+                .push(current_line)
+                .push(next_line),
+            self.line_metrics,
+            TypingTestMessage::LinesMeasured,
+        );
 
         let input_box = TextInput::new(
             &mut self.input_box,
@@ -205,17 +387,35 @@ impl TypingTestState {
         .padding(5)
         .style(theme);
 
-        let submission_wrapper = SubmissionWrapper::new(
+        let mut submission_wrapper = SubmissionWrapper::new(
             input_box,
-            TypingTestMessage::WordSubmitted,
+            TypingTestMessage::WordSubmitted(Separator::Space),
             TypingTestMessage::Redo,
-        );
+        )
+        .allow_corrections(self.confidence_mode.allows_deletion(&self.current_input))
+        .emulate_layout(self.layout_emulation.as_ref());
+
+        // Enter and Tab are only intercepted when the current word needs them, so that they are
+        // ignored as before for generators without lines or indentation
+        if expects_newline {
+            submission_wrapper = submission_wrapper
+                .on_submit_line(TypingTestMessage::WordSubmitted(Separator::Newline));
+        }
+
+        if expects_indent {
+            submission_wrapper = submission_wrapper.on_indent(TypingTestMessage::Indent);
+        }
+
+        if self.test_length_seconds.is_none() {
+            submission_wrapper = submission_wrapper.on_finish(TypingTestMessage::EndTest);
+        }
 
         let wpm_text = if self.show_wpm {
+            let scoring = self.scoring;
             let wpm = self
                 .stats
                 .get_latest_checkpoint()
-                .map(|checkpoint| checkpoint.effective_wpm())
+                .and_then(|checkpoint| scoring.wpm(checkpoint))
                 .unwrap_or_default();
 
             format!("{} WPM", wpm)
@@ -232,7 +432,7 @@ impl TypingTestState {
         .on_press(TypingTestMessage::ToggleWPM);
 
         let timer_text = if self.show_timer {
-            format_time_mm_ss(self.remaining_seconds)
+            format_time_mm_ss(self.timer_seconds)
         } else {
             String::from(" ")
         };
@@ -273,12 +473,34 @@ impl TypingTestState {
             View::Settings,
         )));
 
+        let history_button = Button::new(
+            &mut self.history_button,
+            Text::new("History").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .min_width(100)
+        .style(theme)
+        .on_press(TypingTestMessage::Action(Action::ChangeView(View::History)));
+
+        let lessons_button = Button::new(
+            &mut self.lessons_button,
+            Text::new("Lessons").horizontal_alignment(HorizontalAlignment::Center),
+        )
+        .min_width(100)
+        .style(theme)
+        .on_press(TypingTestMessage::Action(Action::ChangeView(View::Lessons)));
+
+        let navigation = Row::new()
+            .spacing(10)
+            .push(settings_button)
+            .push(history_button)
+            .push(lessons_button);
+
         Column::new()
             .align_items(Align::Center)
             .spacing(20)
             .push(line_display)
             .push(typing_area)
-            .push(settings_button)
+            .push(navigation)
             .push(blank_line())
             .into()
     }
@@ -287,19 +509,185 @@ impl TypingTestState {
         const TICK_DURATION: Duration = Duration::from_millis(100);
 
         match self.status {
-            TypingTestStatus::NotStarted | TypingTestStatus::Finished => iced::Subscription::none(),
+            TypingTestStatus::NotStarted
+            | TypingTestStatus::Finished
+            | TypingTestStatus::Failed => iced::Subscription::none(),
             TypingTestStatus::Started => {
                 iced::time::every(TICK_DURATION).map(TypingTestMessage::TimerTick)
             }
         }
     }
 
-    pub fn update_time_length(&mut self, time: u64) {
+    pub fn update_time_length(&mut self, time: Option<u64>) {
         self.test_length_seconds = time;
         self.reset_test_state(true);
     }
 
+    pub fn update_stop_on_error(&mut self, mode: StopOnError) {
+        self.stop_on_error = mode;
+        self.reset_test_state(true);
+    }
+
+    pub fn update_confidence_mode(&mut self, mode: ConfidenceMode) {
+        self.confidence_mode = mode;
+        self.reset_test_state(true);
+    }
+
+    pub fn update_fail_conditions(&mut self, conditions: FailConditions) {
+        self.fail_conditions = conditions;
+        self.reset_test_state(true);
+    }
+
+    pub fn update_layout_emulation(&mut self, emulation: Option<LayoutEmulation>) {
+        self.layout_emulation = emulation;
+        self.reset_test_state(true);
+    }
+
+    pub fn update_scoring(&mut self, scoring: Scoring) {
+        self.scoring = scoring;
+    }
+
+    pub fn update_generator_kind(&mut self, kind: GeneratorKind) {
+        self.generator_kind = kind;
+        self.rebuild_word_generator();
+    }
+
+    pub fn update_random_word_options(&mut self, options: RandomWordOptions) {
+        self.generator_settings.random_word_options = options;
+        if self.generator_kind == GeneratorKind::RandomWords {
+            self.rebuild_word_generator();
+        }
+    }
+
+    pub fn update_code_language(&mut self, language: CodeLanguage) {
+        self.generator_settings.code_language = language;
+        if self.generator_kind == GeneratorKind::Code {
+            self.rebuild_word_generator();
+        }
+    }
+
+    pub fn update_custom_text(&mut self, text: String) {
+        self.generator_settings.custom_text = text;
+        if self.generator_kind == GeneratorKind::Custom {
+            self.rebuild_word_generator();
+        }
+    }
+
+    pub fn update_custom_text_options(&mut self, options: CustomTextOptions) {
+        self.generator_settings.custom_text_options = options;
+        if self.generator_kind == GeneratorKind::Custom {
+            self.rebuild_word_generator();
+        }
+    }
+
+    pub fn update_letter_set(&mut self, letters: String) {
+        self.generator_settings.letter_set = letters;
+        if self.generator_kind == GeneratorKind::LetterSet {
+            self.rebuild_word_generator();
+        }
+    }
+
+    pub fn update_letter_set_options(&mut self, options: LetterSetOptions) {
+        self.generator_settings.letter_set_options = options;
+        if self.generator_kind == GeneratorKind::LetterSet {
+            self.rebuild_word_generator();
+        }
+    }
+
+    pub fn update_markov_model(&mut self, model: Arc<MarkovModel>) {
+        self.generator_settings.markov_model = model;
+        if self.generator_kind == GeneratorKind::Markov {
+            self.rebuild_word_generator();
+        }
+    }
+
+    pub fn update_markov_mode(&mut self, mode: MarkovMode) {
+        self.generator_settings.markov_mode = mode;
+        if self.generator_kind == GeneratorKind::Markov {
+            self.rebuild_word_generator();
+        }
+    }
+
+    /// Starts a short drill of the words that were missed during a test.
+    ///
+    /// The next new test goes back to the word generator from the settings.
+    pub fn start_practice(&mut self, missed_words: &[MissedWord]) {
+        self.word_gen = Box::new(DrillWordGenerator::from_missed_words(
+            missed_words,
+            PRACTICE_REPETITIONS,
+        ));
+        self.practising = true;
+        self.lesson = None;
+        self.reset_test_state(false);
+    }
+
+    /// Starts a lesson, which only uses the letters unlocked by the lesson.
+    ///
+    /// New tests continue the lesson until the word generator is changed in the settings.
+    pub fn start_lesson(&mut self, id: LessonId, lesson: &Lesson) {
+        self.word_gen = Box::new(LetterSetWordGenerator::new(
+            lesson.letters().iter().copied(),
+            LetterSetOptions::default(),
+        ));
+        self.practising = false;
+        self.lesson = Some(id);
+        self.reset_test_state(true);
+    }
+
+    /// Replaces the word generator using the current settings, then starts a new test.
+    fn rebuild_word_generator(&mut self) {
+        self.practising = false;
+        self.lesson = None;
+        self.word_gen = self.generator_kind.build(&self.generator_settings);
+        self.reset_test_state(true);
+    }
+
+    /// Ends the current test early, recording why it failed.
+    fn fail_test(&mut self, reason: FailReason) -> Command<TypingTestMessage> {
+        self.status = TypingTestStatus::Failed;
+        self.stats.fail(reason);
+        self.display_results()
+    }
+
+    /// Takes the final checkpoint, then signals that the results for the current test should be
+    /// displayed.
+    fn display_results(&mut self) -> Command<TypingTestMessage> {
+        self.stats.finish();
+
+        let result = TestResult::new(self.stats.clone(), self.confidence_mode)
+            .with_emulated_layout(
+                self.layout_emulation
+                    .as_ref()
+                    .map(|emulation| emulation.name().to_string()),
+            )
+            .with_scoring(self.scoring)
+            .with_practice(self.practising)
+            .with_lesson(self.lesson.clone());
+        Command::perform(async move { result }, |result| {
+            TypingTestMessage::Action(Action::DisplayResults(Box::new(result)))
+        })
+    }
+
+    /// Checks whether the current word is submitted with Enter, since it ends a line.
+    fn expects_newline(&self) -> bool {
+        self.current_line
+            .get(self.current_pos)
+            .map_or(false, |w| w.separator == Separator::Newline)
+    }
+
+    /// Checks whether the current word contains indentation, which is typed with Tab.
+    fn expects_indent(&self) -> bool {
+        self.current_line
+            .get(self.current_pos)
+            .map_or(false, |w| w.word.contains('\t'))
+    }
+
     pub fn reset_test_state(&mut self, new_test: bool) {
+        if new_test && self.practising {
+            self.rebuild_word_generator();
+            return;
+        }
+
         if new_test {
             self.word_gen.next_test();
         } else {
@@ -310,27 +698,148 @@ impl TypingTestState {
         self.stats.next_test();
         self.current_pos = 0;
         self.current_input.clear();
-        self.remaining_seconds = self.test_length_seconds;
+        self.timer_seconds = self.test_length_seconds.unwrap_or(0);
+
+        self.line_layout.clear();
+        self.fill_line(false);
+        self.fill_line(true);
+    }
+
+    /// Fills either the current line or the next line with the next words.
+    fn fill_line(&mut self, next: bool) {
+        let line = if next {
+            &mut self.next_line
+        } else {
+            &mut self.current_line
+        };
+
+        self.line_layout.fill_line(
+            self.word_gen.as_mut(),
+            &self.line_metrics.measure,
+            self.line_metrics.max_width,
+            line,
+        );
+    }
+
+    /// Lays out the lines again using new measurements, keeping the same words in the same order.
+    ///
+    /// Words that have been typed on the current line stay where they are, along with the word
+    /// being typed.
+    fn reflow_lines(&mut self, metrics: LineMetrics) {
+        if metrics == self.line_metrics {
+            return;
+        }
+
+        self.line_metrics = metrics;
+
+        let keep = (self.current_pos + 1).min(self.current_line.len());
+        let rest = self.current_line.split_off(keep);
+        self.line_layout
+            .unplace(rest.into_iter().chain(self.next_line.drain(..)));
+
+        self.line_layout.extend_line(
+            self.word_gen.as_mut(),
+            &self.line_metrics.measure,
+            self.line_metrics.max_width,
+            &mut self.current_line,
+        );
+        self.fill_line(true);
+    }
+}
 
-        self.word_gen.fill_line(&mut self.current_line, MAX_CHARS);
-        self.word_gen.fill_line(&mut self.next_line, MAX_CHARS);
+impl GeneratorKind {
+    /// An array of all the kinds of word generator.
+    pub const ALL_KINDS: [GeneratorKind; 6] = [
+        GeneratorKind::RandomWords,
+        GeneratorKind::Passages,
+        GeneratorKind::Code,
+        GeneratorKind::Custom,
+        GeneratorKind::LetterSet,
+        GeneratorKind::Markov,
+    ];
+
+    /// Builds a new word generator of this kind.
+    fn build(self, settings: &GeneratorSettings) -> Box<dyn WordGenerator> {
+        match self {
+            GeneratorKind::RandomWords => Box::new(InfiniteWordGenerator::from(
+                settings.random_word_options.clone(),
+            )),
+            GeneratorKind::Passages => Box::new(PassageWordGenerator::default()),
+            GeneratorKind::Code => Box::new(CodeWordGenerator::from(settings.code_language)),
+            GeneratorKind::Custom => Box::new(CustomWordGenerator::new(
+                &settings.custom_text,
+                settings.custom_text_options,
+            )),
+            GeneratorKind::LetterSet => Box::new(LetterSetWordGenerator::new(
+                settings.letter_set.chars(),
+                settings.letter_set_options,
+            )),
+            GeneratorKind::Markov => Box::new(MarkovWordGenerator::new(
+                Arc::clone(&settings.markov_model),
+                settings.markov_mode,
+            )),
+        }
     }
 }
 
-/// Formats the provided number of seconds into the mm:ss format.
-#[inline]
-fn format_time_mm_ss(seconds: u64) -> String {
-    format!("{:0>2}:{:0>2}", seconds / 60, seconds % 60)
+impl Default for GeneratorKind {
+    fn default() -> Self {
+        GeneratorKind::RandomWords
+    }
+}
+
+impl Display for GeneratorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            GeneratorKind::RandomWords => f.write_str("Random Words"),
+            GeneratorKind::Passages => f.write_str("Passages"),
+            GeneratorKind::Code => f.write_str("Code Snippets"),
+            GeneratorKind::Custom => f.write_str("Custom Text"),
+            GeneratorKind::LetterSet => f.write_str("Letter Set"),
+            GeneratorKind::Markov => f.write_str("Markov Chain"),
+        }
+    }
 }
 
+impl Default for GeneratorSettings {
+    fn default() -> Self {
+        Self {
+            random_word_options: RandomWordOptions::default(),
+            code_language: CodeLanguage::default(),
+            custom_text: String::new(),
+            custom_text_options: CustomTextOptions::default(),
+            letter_set: String::from(DEFAULT_LETTER_SET),
+            letter_set_options: LetterSetOptions::default(),
+            markov_model: Arc::new(MarkovModel::default()),
+            markov_mode: MarkovMode::default(),
+        }
+    }
+}
+
+/// Creates an empty line with the same height as a line of words.
 #[inline]
-fn blank_line() -> Text {
-    Text::new(" ".repeat(MAX_CHARS + 1))
+pub fn blank_line() -> Text {
+    Text::new(" ")
         .font(Theme::monospace_font())
-        .size(22)
+        .size(WORD_TEXT_SIZE)
+}
+
+/// Wraps lines of words so that they are measured using the font and text size for words.
+pub fn measured_lines<'a, Message: 'a>(
+    lines: Column<'a, Message>,
+    metrics: LineMetrics,
+    on_measured: fn(LineMetrics) -> Message,
+) -> Element<'a, Message> {
+    MeasuredLines::new(lines, metrics, on_measured)
+        .font(Theme::monospace_font())
+        .text_size(WORD_TEXT_SIZE)
+        .max_columns(MAX_CHARS)
+        .into()
 }
 
 /// Converts a [DisplayedWord] to an [iced::Text].
+///
+/// Words that are submitted with a newline are followed by a marker, since they end the line.
 fn displayed_word(word: &DisplayedWord, theme: &Box<dyn ApplicationTheme>) -> Text {
     let theme = theme.word_palette();
     let color = match word.status {
@@ -339,18 +848,24 @@ fn displayed_word(word: &DisplayedWord, theme: &Box<dyn ApplicationTheme>) -> Te
         WordStatus::Incorrect => theme.incorrect,
     };
 
-    Text::new(&word.word)
+    // Tabs are expanded manually, since they are not rendered with a consistent width
+    let mut text = word.word.replace('\t', &" ".repeat(TAB_WIDTH));
+    if word.separator == Separator::Newline {
+        text.push(NEWLINE_MARKER);
+    }
+
+    Text::new(text)
         .color(color)
         .font(Theme::monospace_font())
-        .size(22)
+        .size(WORD_TEXT_SIZE)
 }
 
 /// Converts a list of [DisplayedWord]s into a line of [iced::Text]s.
-fn line_of_displayed_words<'a>(
+pub fn line_of_displayed_words<'a, Message: 'a>(
     words: &'a [DisplayedWord],
     current_pos: usize,
     theme: &'a Box<dyn ApplicationTheme>,
-) -> Row<'a, TypingTestMessage> {
+) -> Row<'a, Message> {
     let mut elements_iter = words.iter().enumerate().map(|(pos, w)| -> Element<_> {
         let text = displayed_word(w, theme);
         if current_pos == pos {
@@ -1 +1,3 @@
+pub mod keyboard_heatmap;
+pub mod measured_lines;
 pub mod word_submission;
@@ -0,0 +1,229 @@
+use std::{collections::HashMap, fmt::Display};
+
+use iced::{
+    canvas::{Cursor, Frame, Geometry, Program, Text},
+    HorizontalAlignment, Point, Rectangle, Size, VerticalAlignment,
+};
+use typetest_core::{
+    layouts::{KeyboardLayout, LayoutKey, Row, MAX_ROW_KEYS},
+    stats::keys::KeyStats,
+};
+use typetest_themes::HeatmapPalette;
+
+/// The width of the keyboard, in key widths.
+const KEYBOARD_WIDTH: f32 = 15.0;
+
+/// The number of rows on the keyboard.
+const KEYBOARD_ROWS: f32 = 5.0;
+
+/// The gap between each key, in pixels.
+const KEY_GAP: f32 = 2.0;
+
+/// Represents a key on the on-screen keyboard.
+struct Key {
+    label: String,
+    chars: Vec<char>,
+    width: f32,
+}
+
+/// Represents a row of keys, which is offset from the left edge of the keyboard.
+struct KeyRow {
+    offset: f32,
+    keys: Vec<Key>,
+}
+
+impl Key {
+    /// Creates a key that is labelled with its unshifted character.
+    fn from_layout(key: &LayoutKey) -> Self {
+        Self {
+            label: key.unshifted.to_uppercase().to_string(),
+            chars: std::iter::once(key.unshifted).chain(key.shifted).collect(),
+            width: 1.0,
+        }
+    }
+
+    /// Creates a key that isn't part of the layout, such as Tab or Enter.
+    fn special(label: &str, c: char, width: f32) -> Self {
+        Self {
+            label: label.to_string(),
+            chars: vec![c],
+            width,
+        }
+    }
+}
+
+/// Builds the rows of an ANSI keyboard for a layout, including the keys for each separator.
+fn keyboard_rows(layout: &KeyboardLayout) -> Vec<KeyRow> {
+    let layout_keys =
+        |row: Row| -> Vec<Key> { layout.keys(row).iter().map(Key::from_layout).collect() };
+
+    let number = layout_keys(Row::Number);
+
+    let mut top = vec![Key::special("Tab", '\t', 1.5)];
+    top.extend(layout_keys(Row::Top));
+    if top.len() > MAX_ROW_KEYS {
+        // The key above Enter is wider to line up with the end of the keyboard
+        if let Some(last) = top.last_mut() {
+            last.width = 1.5;
+        }
+    }
+
+    let mut home = layout_keys(Row::Home);
+    home.push(Key::special("Enter", '\n', 2.25));
+
+    let bottom = layout_keys(Row::Bottom);
+    let space = vec![Key::special("Space", ' ', 6.25)];
+
+    vec![
+        KeyRow {
+            offset: 0.0,
+            keys: number,
+        },
+        KeyRow {
+            offset: 0.0,
+            keys: top,
+        },
+        KeyRow {
+            offset: 1.75,
+            keys: home,
+        },
+        KeyRow {
+            offset: 2.25,
+            keys: bottom,
+        },
+        KeyRow {
+            offset: 3.75,
+            keys: space,
+        },
+    ]
+}
+
+/// Represents the statistic that is shown on a keyboard heatmap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeatmapMetric {
+    ErrorRate,
+    Latency,
+}
+
+/// An on-screen keyboard drawn on a canvas, where each key is coloured by one of its statistics.
+///
+/// Keys are labelled using the provided layout, but are always arranged in an ANSI shape.
+/// Colours are relative, ramping from the key with the best value to the key with the worst value.
+/// Keys that were never typed are shown using the palette's colour for missing data.
+pub struct KeyboardHeatmap<'a> {
+    key_stats: &'a HashMap<char, KeyStats>,
+    rows: Vec<KeyRow>,
+    metric: HeatmapMetric,
+    palette: HeatmapPalette,
+}
+
+impl HeatmapMetric {
+    /// An array of all the heatmap metrics.
+    pub const ALL_METRICS: [HeatmapMetric; 2] = [HeatmapMetric::ErrorRate, HeatmapMetric::Latency];
+
+    /// Gets the value of this metric for a key, where higher values are worse.
+    fn value(self, stats: &KeyStats) -> Option<f32> {
+        match self {
+            HeatmapMetric::ErrorRate if stats.presses > 0 => Some(stats.error_rate()),
+            HeatmapMetric::ErrorRate => None,
+            HeatmapMetric::Latency => stats.average_latency().map(|l| l.as_secs_f32()),
+        }
+    }
+}
+
+impl Default for HeatmapMetric {
+    fn default() -> Self {
+        HeatmapMetric::ErrorRate
+    }
+}
+
+impl Display for HeatmapMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            HeatmapMetric::ErrorRate => f.write_str("Error Rate"),
+            HeatmapMetric::Latency => f.write_str("Average Latency"),
+        }
+    }
+}
+
+impl<'a> KeyboardHeatmap<'a> {
+    /// The ratio between the width and height of the keyboard.
+    pub const ASPECT_RATIO: f32 = KEYBOARD_WIDTH / KEYBOARD_ROWS;
+
+    /// Creates a new [`KeyboardHeatmap`].
+    pub fn new(
+        key_stats: &'a HashMap<char, KeyStats>,
+        layout: &KeyboardLayout,
+        metric: HeatmapMetric,
+        palette: HeatmapPalette,
+    ) -> Self {
+        Self {
+            key_stats,
+            rows: keyboard_rows(layout),
+            metric,
+            palette,
+        }
+    }
+
+    /// Gets the value of the current metric for a key, combining the statistics for each of its characters.
+    fn key_value(&self, key: &Key) -> Option<f32> {
+        let mut stats = KeyStats::default();
+        for c in key.chars.iter() {
+            if let Some(char_stats) = self.key_stats.get(c) {
+                stats.merge(char_stats);
+            }
+        }
+
+        self.metric.value(&stats)
+    }
+}
+
+impl<'a, Message> Program<Message> for KeyboardHeatmap<'a> {
+    fn draw(&self, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        let mut frame = Frame::new(bounds.size());
+        let unit = (frame.width() / KEYBOARD_WIDTH).min(frame.height() / KEYBOARD_ROWS);
+
+        let values: Vec<Vec<Option<f32>>> = self
+            .rows
+            .iter()
+            .map(|row| row.keys.iter().map(|key| self.key_value(key)).collect())
+            .collect();
+
+        let (min, max) = values
+            .iter()
+            .flatten()
+            .flatten()
+            .fold((f32::MAX, f32::MIN), |(min, max), v| {
+                (min.min(*v), max.max(*v))
+            });
+
+        for (i, (row, row_values)) in self.rows.iter().zip(values.iter()).enumerate() {
+            let y = i as f32 * unit;
+            let mut x = row.offset * unit;
+
+            for (key, value) in row.keys.iter().zip(row_values.iter()) {
+                let size = Size::new(key.width * unit - KEY_GAP, unit - KEY_GAP);
+                let color = match value {
+                    Some(v) if max > min => self.palette.ramp((v - min) / (max - min)),
+                    Some(_) => self.palette.ramp(0.0),
+                    None => self.palette.no_data,
+                };
+
+                frame.fill_rectangle(Point::new(x, y), size, color);
+                frame.fill_text(Text {
+                    content: key.label.clone(),
+                    position: Point::new(x + size.width / 2.0, y + size.height / 2.0),
+                    color: self.palette.text,
+                    size: unit * 0.4,
+                    horizontal_alignment: HorizontalAlignment::Center,
+                    vertical_alignment: VerticalAlignment::Center,
+                    ..Text::default()
+                });
+
+                x += key.width * unit;
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
@@ -0,0 +1,207 @@
+use std::hash::Hash;
+
+use iced::{Length, Point, Rectangle, Size};
+use iced_native::{event, layout, text, Clipboard, Element, Event, Hasher, Layout, Widget};
+use typetest_core::line_layout::MonospaceMeasure;
+
+/// The text that is measured to find the width of each character.
+const SAMPLE_TEXT: &str = "0123456789";
+
+/// Represents the measurements used to lay out lines of words.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineMetrics {
+    /// The width of the characters in each word, and the gaps between them.
+    pub measure: MonospaceMeasure,
+
+    /// The maximum width of each line.
+    pub max_width: f32,
+}
+
+/// Native-only wrapper for the lines of words in a typing test. It fills the available width, and
+/// measures the font used for the words whenever it receives an event. If the width or the font
+/// measurements change, it sends a message so that the lines can be laid out again.
+///
+/// The content is centered using the current maximum line width.
+pub struct MeasuredLines<'a, Message, Renderer>
+where
+    Renderer: text::Renderer,
+{
+    content: Element<'a, Message, Renderer>,
+    metrics: LineMetrics,
+    on_measured: Box<dyn Fn(LineMetrics) -> Message + 'a>,
+    font: Renderer::Font,
+    text_size: u16,
+    max_columns: usize,
+}
+
+impl LineMetrics {
+    /// Creates metrics which measure lines in columns, for use until the font has been measured.
+    pub fn columns(max_columns: usize) -> Self {
+        Self {
+            measure: MonospaceMeasure::COLUMNS,
+            max_width: max_columns as f32,
+        }
+    }
+}
+
+impl<'a, Message, Renderer> MeasuredLines<'a, Message, Renderer>
+where
+    Renderer: text::Renderer,
+{
+    /// Creates a new [`MeasuredLines`] with the metrics that the content was laid out with.
+    pub fn new<F>(
+        content: impl Into<Element<'a, Message, Renderer>>,
+        metrics: LineMetrics,
+        on_measured: F,
+    ) -> Self
+    where
+        F: 'a + Fn(LineMetrics) -> Message,
+    {
+        Self {
+            content: content.into(),
+            metrics,
+            on_measured: Box::new(on_measured),
+            font: Default::default(),
+            text_size: 20,
+            max_columns: usize::MAX,
+        }
+    }
+
+    /// Sets the font used to display the words.
+    pub fn font(mut self, font: Renderer::Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Sets the text size used to display the words.
+    pub fn text_size(mut self, size: u16) -> Self {
+        self.text_size = size;
+        self
+    }
+
+    /// Sets the maximum number of characters on each line, even if there is space for more.
+    pub fn max_columns(mut self, max_columns: usize) -> Self {
+        self.max_columns = max_columns;
+        self
+    }
+
+    /// Measures the font to find the metrics for lines within the available width.
+    ///
+    /// Returns `None` if the renderer can't measure text.
+    fn measure(&self, renderer: &Renderer, available_width: f32) -> Option<LineMetrics> {
+        let (sample_width, _) =
+            renderer.measure(SAMPLE_TEXT, self.text_size, self.font, Size::INFINITY);
+        if sample_width <= 0.0 {
+            return None;
+        }
+
+        // Gaps between words use the default text size
+        let (gap_width, _) =
+            renderer.measure(" ", renderer.default_size(), self.font, Size::INFINITY);
+
+        let char_width = sample_width / SAMPLE_TEXT.len() as f32;
+        let max_width = available_width.min(self.max_columns as f32 * char_width);
+
+        Some(LineMetrics {
+            measure: MonospaceMeasure {
+                char_width,
+                gap_width,
+            },
+            max_width,
+        })
+    }
+}
+
+impl<'a, Message, Renderer> From<MeasuredLines<'a, Message, Renderer>>
+    for Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: 'a + text::Renderer,
+{
+    fn from(widget: MeasuredLines<'a, Message, Renderer>) -> Self {
+        Element::new(widget)
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for MeasuredLines<'a, Message, Renderer>
+where
+    Renderer: text::Renderer,
+{
+    fn width(&self) -> Length {
+        Length::Fill
+    }
+
+    fn height(&self) -> Length {
+        Length::Shrink
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits.width(Length::Fill).height(Length::Shrink);
+        let mut content = self.content.layout(renderer, &limits.loose());
+        let width = limits.max().width;
+
+        // The lines may have been laid out with older metrics, but they are centered using the
+        // current width so that they don't jump once they are laid out again
+        let max_width = self
+            .measure(renderer, width)
+            .map_or(self.metrics.max_width, |metrics| metrics.max_width);
+        let offset = ((width - max_width) / 2.0).max(0.0);
+        content.move_to(Point::new(offset, 0.0));
+
+        layout::Node::with_children(Size::new(width, content.size().height), vec![content])
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        defaults: &Renderer::Defaults,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) -> Renderer::Output {
+        self.content.draw(
+            renderer,
+            defaults,
+            layout.children().next().unwrap(),
+            cursor_position,
+            viewport,
+        )
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        struct Marker;
+        std::any::TypeId::of::<Marker>().hash(state);
+
+        self.metrics.max_width.to_bits().hash(state);
+        self.content.hash_layout(state);
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        messages: &mut Vec<Message>,
+    ) -> event::Status {
+        let status = self.content.on_event(
+            event,
+            layout.children().next().unwrap(),
+            cursor_position,
+            renderer,
+            clipboard,
+            messages,
+        );
+
+        // Every event is checked, since there isn't an event for the font being loaded
+        if let Some(metrics) = self.measure(renderer, layout.bounds().width) {
+            if metrics != self.metrics {
+                self.metrics = metrics;
+                messages.push((self.on_measured)(metrics));
+            }
+        }
+
+        status
+    }
+}
@@ -3,9 +3,14 @@ use iced_native::{
     event, keyboard, text_input, Clipboard, Element, Event, Layout, TextInput, Widget,
 };
 use keyboard::{KeyCode, Modifiers};
+use typetest_core::layouts::LayoutEmulation;
 
 /// Native-only wrapper for word submission. It provides the following functionality:
 /// - Intercept spacebar inputs, sending a message indicating that the current word is being submitted.
+/// - Optionally intercept Enter and Tab inputs, for submitting lines and inserting indentation respectively.
+/// - Optionally intercept Shift + Enter, sending a message indicating that the test should end.
+/// - Optionally intercept Backspace, Delete and cutting with Ctrl + X, to disable corrections.
+/// - Optionally remap typed characters to emulate a different keyboard layout.
 /// - Redo the test when one of the following hotkeys is used:
 ///   - Ctrl + R (or Cmd + R on Mac OS)
 ///   - F5
@@ -15,6 +20,11 @@ where
 {
     text_input: TextInput<'a, Message, Renderer>,
     on_submit_word: Message,
+    on_submit_line: Option<Message>,
+    on_finish: Option<Message>,
+    on_indent: Option<Message>,
+    allow_corrections: bool,
+    emulation: Option<&'a LayoutEmulation>,
     on_redo: Message,
 }
 
@@ -31,9 +41,48 @@ where
         Self {
             text_input,
             on_submit_word,
+            on_submit_line: None,
+            on_finish: None,
+            on_indent: None,
+            allow_corrections: true,
+            emulation: None,
             on_redo,
         }
     }
+
+    /// Sets the message that is produced when Enter is pressed to submit the current line.
+    pub fn on_submit_line(mut self, message: Message) -> Self {
+        self.on_submit_line = Some(message);
+        self
+    }
+
+    /// Sets the message that is produced when Shift + Enter is pressed to end the test.
+    pub fn on_finish(mut self, message: Message) -> Self {
+        self.on_finish = Some(message);
+        self
+    }
+
+    /// Sets the message that is produced when Tab is pressed to insert indentation.
+    pub fn on_indent(mut self, message: Message) -> Self {
+        self.on_indent = Some(message);
+        self
+    }
+
+    /// Sets whether Backspace, Delete and cutting can be used to correct the input.
+    pub fn allow_corrections(mut self, allow_corrections: bool) -> Self {
+        self.allow_corrections = allow_corrections;
+        self
+    }
+
+    /// Sets the layout emulation used to remap typed characters before they reach the input.
+    ///
+    /// Only character events are remapped, since the keys used for shortcuts and editing are in the
+    /// same positions on every layout. Key presses aren't remapped, because a character event
+    /// follows them whenever they type something, so input from dead keys or an IME is unchanged.
+    pub fn emulate_layout(mut self, emulation: Option<&'a LayoutEmulation>) -> Self {
+        self.emulation = emulation;
+        self
+    }
 }
 
 impl<'a, Message, Renderer> From<SubmissionWrapper<'a, Message, Renderer>>
@@ -99,6 +148,13 @@ where
         clipboard: &mut dyn Clipboard,
         messages: &mut Vec<Message>,
     ) -> event::Status {
+        let event = match (event, self.emulation) {
+            (Event::Keyboard(keyboard::Event::CharacterReceived(c)), Some(emulation)) => {
+                Event::Keyboard(keyboard::Event::CharacterReceived(emulation.remap(c)))
+            }
+            (event, _) => event,
+        };
+
         match event {
             Event::Keyboard(
                 keyboard::Event::KeyPressed {
@@ -117,6 +173,49 @@ where
                 messages.push(self.on_submit_word.clone());
                 event::Status::Captured
             }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Enter,
+                modifiers: Modifiers { shift: true, .. },
+            })
+            | Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::NumpadEnter,
+                modifiers: Modifiers { shift: true, .. },
+            }) if self.on_finish.is_some() => {
+                messages.extend(self.on_finish.clone());
+                event::Status::Captured
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Enter,
+                ..
+            })
+            | Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::NumpadEnter,
+                ..
+            }) if self.on_submit_line.is_some() => {
+                messages.extend(self.on_submit_line.clone());
+                event::Status::Captured
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Tab,
+                ..
+            }) if self.on_indent.is_some() => {
+                messages.extend(self.on_indent.clone());
+                event::Status::Captured
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Backspace,
+                ..
+            })
+            | Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Delete,
+                ..
+            }) if !self.allow_corrections => event::Status::Captured,
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::X,
+                modifiers,
+            }) if !self.allow_corrections && modifiers.is_command_pressed() => {
+                event::Status::Captured
+            }
             _ => Widget::<Message, Renderer>::on_event(
                 &mut self.text_input,
                 event,
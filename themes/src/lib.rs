@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use iced_core::{Color, Font};
-use iced_style::{button, container, pick_list, radio, scrollable, text_input};
+use iced_style::{button, checkbox, container, pick_list, radio, scrollable, text_input};
 
 pub mod themes;
 
@@ -50,11 +50,32 @@ pub struct WordPalette {
     pub incorrect: Color,
 }
 
+/// Represents the colours used for heatmaps, which ramp from the best values to the worst values.
+pub struct HeatmapPalette {
+    pub best: Color,
+    pub worst: Color,
+    pub no_data: Color,
+    pub text: Color,
+}
+
+impl HeatmapPalette {
+    /// Interpolates between the best and worst colours, where `t` is clamped between 0 and 1.
+    pub fn ramp(&self, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        Color::from_rgb(
+            self.best.r + (self.worst.r - self.best.r) * t,
+            self.best.g + (self.worst.g - self.best.g) * t,
+            self.best.b + (self.worst.b - self.best.b) * t,
+        )
+    }
+}
+
 /// Trait that needs to be implemented for any themes in the application.
 /// Once implemented, the theme can be used for any widgets used by the GUI.
 pub trait ApplicationTheme {
     // Built-In Widgets
     fn button(&self) -> Box<dyn button::StyleSheet>;
+    fn checkbox(&self) -> Box<dyn checkbox::StyleSheet>;
     fn container(&self) -> Box<dyn container::StyleSheet>;
     fn pick_list(&self) -> Box<dyn pick_list::StyleSheet>;
     fn radio(&self) -> Box<dyn radio::StyleSheet>;
@@ -64,6 +85,7 @@ pub trait ApplicationTheme {
     // Custom Widgets
     fn word_palette(&self) -> WordPalette;
     fn word_background(&self) -> Box<dyn container::StyleSheet>;
+    fn heatmap_palette(&self) -> HeatmapPalette;
 }
 
 impl From<Theme> for Box<dyn ApplicationTheme> {
@@ -82,6 +104,13 @@ impl From<&Box<dyn ApplicationTheme>> for Box<dyn button::StyleSheet> {
     }
 }
 
+impl From<&Box<dyn ApplicationTheme>> for Box<dyn checkbox::StyleSheet> {
+    #[inline]
+    fn from(theme: &Box<dyn ApplicationTheme>) -> Self {
+        theme.checkbox()
+    }
+}
+
 impl From<&Box<dyn ApplicationTheme>> for Box<dyn container::StyleSheet> {
     #[inline]
     fn from(theme: &Box<dyn ApplicationTheme>) -> Self {
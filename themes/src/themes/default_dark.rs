@@ -1,4 +1,4 @@
-use crate::{ApplicationTheme, WordPalette};
+use crate::{ApplicationTheme, HeatmapPalette, WordPalette};
 
 use iced_core::Background;
 use iced_style::{
@@ -42,6 +42,13 @@ const WORD_PALETTE: WordPalette = WordPalette {
     incorrect: Color::from_rgb(1.0, 0.0, 0.0),
 };
 
+const HEATMAP_PALETTE: HeatmapPalette = HeatmapPalette {
+    best: Color::from_rgb(0.0, 0.55, 0.45),
+    worst: Color::from_rgb(0.85, 0.15, 0.15),
+    no_data: SURFACE,
+    text: Color::WHITE,
+};
+
 /// The dark theme from iced's [styling example].
 ///
 /// [styling example]: https://github.com/hecrj/iced/tree/master/examples/styling
@@ -52,6 +59,10 @@ impl ApplicationTheme for DefaultDark {
         Button.into()
     }
 
+    fn checkbox(&self) -> Box<dyn checkbox::StyleSheet> {
+        Checkbox.into()
+    }
+
     fn container(&self) -> Box<dyn container::StyleSheet> {
         Container.into()
     }
@@ -79,6 +90,10 @@ impl ApplicationTheme for DefaultDark {
     fn word_background(&self) -> Box<dyn container::StyleSheet> {
         WordBackground.into()
     }
+
+    fn heatmap_palette(&self) -> HeatmapPalette {
+        HEATMAP_PALETTE
+    }
 }
 
 pub struct Container;
@@ -1,7 +1,7 @@
 use iced_core::{Background, Color};
 use iced_style::container;
 
-use crate::{ApplicationTheme, WordPalette};
+use crate::{ApplicationTheme, HeatmapPalette, WordPalette};
 
 const WORD_PALETTE: WordPalette = WordPalette {
     default: Color::BLACK,
@@ -9,6 +9,13 @@ const WORD_PALETTE: WordPalette = WordPalette {
     incorrect: Color::from_rgb(0.75, 0.0, 0.0),
 };
 
+const HEATMAP_PALETTE: HeatmapPalette = HeatmapPalette {
+    best: Color::from_rgb(0.6, 0.9, 0.65),
+    worst: Color::from_rgb(0.95, 0.4, 0.35),
+    no_data: Color::from_rgb(0.9, 0.9, 0.9),
+    text: Color::BLACK,
+};
+
 #[derive(Clone, Copy, Debug)]
 pub struct DefaultLight;
 
@@ -17,6 +24,10 @@ impl ApplicationTheme for DefaultLight {
         Default::default()
     }
 
+    fn checkbox(&self) -> Box<dyn iced_style::checkbox::StyleSheet> {
+        Default::default()
+    }
+
     fn container(&self) -> Box<dyn iced_style::container::StyleSheet> {
         Default::default()
     }
@@ -44,6 +55,10 @@ impl ApplicationTheme for DefaultLight {
     fn word_background(&self) -> Box<dyn iced_style::container::StyleSheet> {
         WordBackground.into()
     }
+
+    fn heatmap_palette(&self) -> HeatmapPalette {
+        HEATMAP_PALETTE
+    }
 }
 
 pub struct WordBackground;